@@ -0,0 +1,277 @@
+//! Tar and zip archive reading and writing.
+//!
+//! Lets a tar (optionally Gzip/Zstd-wrapped) [`crate::file::File`] be
+//! unpacked into its member files via [`crate::file::File::tar_entries`] (or,
+//! without buffering every member up front,
+//! [`crate::file::File::tar_entries_stream`]) and built back up from a slice
+//! of files via [`crate::file::File::from_tar`].
+//!
+//! [`crate::file::File::list_archive`] and [`crate::file::File::extract_entry`]
+//! additionally treat zip archives as containers without requiring the
+//! caller to build a full `Vec<File>` up front.
+
+use std::io::Read;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+use crate::error::{FileError, Result};
+
+/// One entry read out of a tar archive, ready to seed a [`crate::file::File`]
+/// via `from_bytes`.
+pub struct ArchiveEntry {
+    /// The entry's path within the archive, used as the member file's name/path.
+    pub path: String,
+    /// The entry's size in bytes.
+    pub size: u64,
+    /// The entry's modification time, if the archive records one.
+    pub mtime: Option<DateTime<Utc>>,
+    /// The entry's raw contents.
+    pub data: Bytes,
+}
+
+/// An entry to write into a tar archive.
+pub struct ArchiveInput {
+    /// The archive path to write this entry under.
+    pub path: String,
+    /// The modification time to record for this entry, if any.
+    pub mtime: Option<DateTime<Utc>>,
+    /// The entry's raw contents.
+    pub data: Bytes,
+}
+
+/// Walk a tar byte stream one entry at a time, invoking `visit` as each
+/// member is read instead of collecting the whole archive into memory
+/// first. Entries are read from `reader` in order, and all-zero header
+/// blocks are skipped rather than treated as the end of the stream, so this
+/// also handles concatenated archives and the trailing all-zero blocks that
+/// terminate a tar stream.
+pub fn visit_tar_entries<R: Read>(
+    reader: R,
+    mut visit: impl FnMut(ArchiveEntry) -> Result<()>,
+) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    archive.set_ignore_zeros(true);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().to_string();
+        let size = entry.header().size()?;
+        let mtime = entry
+            .header()
+            .mtime()
+            .ok()
+            .and_then(|secs| DateTime::from_timestamp(secs as i64, 0));
+
+        let mut buf = Vec::with_capacity(size as usize);
+        entry.read_to_end(&mut buf)?;
+
+        visit(ArchiveEntry {
+            path,
+            size,
+            mtime,
+            data: Bytes::from(buf),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Parse a tar byte stream into its member entries.
+pub fn read_tar_entries(data: &[u8]) -> Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::new();
+    visit_tar_entries(data, |entry| {
+        entries.push(entry);
+        Ok(())
+    })?;
+    Ok(entries)
+}
+
+/// Write a set of entries into a tar byte stream.
+pub fn write_tar(inputs: Vec<ArchiveInput>) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+
+    for input in inputs {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(input.data.len() as u64);
+        header.set_mtime(input.mtime.map(|dt| dt.timestamp().max(0) as u64).unwrap_or(0));
+        header.set_mode(0o644);
+        header.set_cksum();
+
+        builder.append_data(&mut header, &input.path, input.data.as_ref())?;
+    }
+
+    builder.into_inner().map_err(Into::into)
+}
+
+/// The archive container format detected by [`detect_archive_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// A POSIX/GNU tar archive.
+    Tar,
+    /// A zip archive.
+    Zip,
+}
+
+/// A single member's path, size, and modification time, as returned by
+/// [`list_entries`].
+pub struct ArchiveEntryInfo {
+    /// The entry's path within the archive.
+    pub path: String,
+    /// The entry's uncompressed size in bytes.
+    pub size: u64,
+    /// The entry's modification time, if the archive records one.
+    pub mtime: Option<DateTime<Utc>>,
+}
+
+/// The default cap on a single extracted entry's size, guarding against zip/tar bombs.
+pub const DEFAULT_MAX_ENTRY_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Detect whether `data` is a tar or zip archive, preferring the declared
+/// `mime_type` and falling back to magic-byte sniffing.
+pub fn detect_archive_kind(mime_type: Option<&str>, data: &[u8]) -> Option<ArchiveKind> {
+    match mime_type {
+        Some("application/zip") => return Some(ArchiveKind::Zip),
+        Some("application/x-tar") => return Some(ArchiveKind::Tar),
+        _ => {}
+    }
+
+    if data.starts_with(b"PK\x03\x04") || data.starts_with(b"PK\x05\x06") {
+        return Some(ArchiveKind::Zip);
+    }
+    if data.len() > 262 && &data[257..262] == b"ustar" {
+        return Some(ArchiveKind::Tar);
+    }
+
+    None
+}
+
+/// List an archive's member entries.
+///
+/// Zip members are listed from the archive's directory without reading
+/// their contents. Tar members are parsed with [`read_tar_entries`], which
+/// reads every member's contents into memory before they are dropped.
+pub fn list_entries(kind: ArchiveKind, data: &[u8]) -> Result<Vec<ArchiveEntryInfo>> {
+    match kind {
+        ArchiveKind::Tar => read_tar_entries(data).map(|entries| {
+            entries
+                .into_iter()
+                .map(|e| ArchiveEntryInfo {
+                    path: e.path,
+                    size: e.size,
+                    mtime: e.mtime,
+                })
+                .collect()
+        }),
+        ArchiveKind::Zip => {
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data))?;
+            let mut entries = Vec::with_capacity(archive.len());
+            for i in 0..archive.len() {
+                let file = archive.by_index(i)?;
+                entries.push(ArchiveEntryInfo {
+                    path: file.name().to_string(),
+                    size: file.size(),
+                    mtime: file
+                        .last_modified()
+                        .and_then(|dt| {
+                            chrono::NaiveDate::from_ymd_opt(
+                                dt.year() as i32,
+                                dt.month() as u32,
+                                dt.day() as u32,
+                            )
+                            .and_then(|d| {
+                                d.and_hms_opt(
+                                    dt.hour() as u32,
+                                    dt.minute() as u32,
+                                    dt.second() as u32,
+                                )
+                            })
+                        })
+                        .map(|naive| naive.and_utc()),
+                });
+            }
+            Ok(entries)
+        }
+    }
+}
+
+/// Extract a single named entry's contents from an archive.
+///
+/// Rejects entries whose normalized path would escape the archive root
+/// ("zip slip") and caps extraction at `max_size` bytes to guard against
+/// decompression bombs.
+pub fn extract_entry(kind: ArchiveKind, data: &[u8], name: &str, max_size: u64) -> Result<Bytes> {
+    match kind {
+        ArchiveKind::Tar => {
+            let mut archive = tar::Archive::new(data);
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let path = entry.path()?.to_string_lossy().to_string();
+                if path != name {
+                    continue;
+                }
+                if !is_safe_entry_path(&path) {
+                    return Err(FileError::InvalidSource(format!(
+                        "Archive entry path escapes the archive root: {}",
+                        path
+                    )));
+                }
+                return read_capped(&mut entry, max_size);
+            }
+            Err(FileError::InvalidSource(format!(
+                "Archive entry not found: {}",
+                name
+            )))
+        }
+        ArchiveKind::Zip => {
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data))?;
+            let mut file = archive
+                .by_name(name)
+                .map_err(|e| FileError::InvalidSource(format!("Archive entry not found: {}", e)))?;
+            if !is_safe_entry_path(file.name()) {
+                return Err(FileError::InvalidSource(format!(
+                    "Archive entry path escapes the archive root: {}",
+                    file.name()
+                )));
+            }
+            read_capped(&mut file, max_size)
+        }
+    }
+}
+
+/// Read from `reader` up to `max_size + 1` bytes, returning an error if that
+/// cap is exceeded (guards against a spoofed/expanding size header).
+fn read_capped<R: Read>(reader: &mut R, max_size: u64) -> Result<Bytes> {
+    let mut buf = Vec::new();
+    reader.take(max_size + 1).read_to_end(&mut buf)?;
+    if buf.len() as u64 > max_size {
+        return Err(FileError::InvalidSource(format!(
+            "Archive entry exceeds the maximum allowed size of {} bytes",
+            max_size
+        )));
+    }
+    Ok(Bytes::from(buf))
+}
+
+/// Reject entry paths that are absolute or whose `..` components would
+/// traverse above the archive root ("zip slip").
+fn is_safe_entry_path(path: &str) -> bool {
+    if path.starts_with('/') || path.starts_with('\\') {
+        return false;
+    }
+
+    let mut depth: i32 = 0;
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => depth += 1,
+        }
+    }
+    true
+}
@@ -1,15 +1,115 @@
 //! Content-Disposition header parsing.
 //!
-//! Extracts the filename from HTTP Content-Disposition headers following
-//! RFC 6266 / RFC 2616 patterns.
+//! Extracts the disposition type and parameters from HTTP Content-Disposition
+//! headers following RFC 6266 / RFC 2616 patterns.
+
+/// The disposition type of a Content-Disposition header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DispositionType {
+    /// `inline` - content displayed within the page.
+    Inline,
+    /// `attachment` - content should be downloaded.
+    Attachment,
+    /// `form-data` - a part of a `multipart/form-data` body.
+    FormData,
+    /// An unrecognized disposition token, preserved verbatim (lowercased).
+    Ext(String),
+}
+
+impl DispositionType {
+    fn parse(token: &str) -> Self {
+        match token {
+            "inline" => DispositionType::Inline,
+            "attachment" => DispositionType::Attachment,
+            "form-data" => DispositionType::FormData,
+            other => DispositionType::Ext(other.to_string()),
+        }
+    }
+
+    /// Returns the wire representation of this disposition type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DispositionType::Inline => "inline",
+            DispositionType::Attachment => "attachment",
+            DispositionType::FormData => "form-data",
+            DispositionType::Ext(s) => s,
+        }
+    }
+}
+
+/// A single Content-Disposition parameter, e.g. `filename="example.txt"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DispositionParam {
+    /// The parameter name, lowercased (e.g. "filename", "name").
+    pub name: String,
+    /// The parameter value, already unquoted/decoded.
+    pub value: String,
+}
 
 /// Parsed content disposition data.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContentDisposition {
-    /// The disposition type (e.g., "attachment", "inline").
-    pub disposition_type: String,
-    /// The filename parameter, if present.
-    pub filename: Option<String>,
+    /// The disposition type (e.g., attachment, inline, form-data).
+    pub disposition_type: DispositionType,
+    /// All parameters in the order they were resolved, including `filename`
+    /// and `name` when present.
+    pub params: Vec<DispositionParam>,
+    /// The RFC 5987/2231 language tag carried alongside an extended
+    /// (`filename*`) value, if any (e.g. "en").
+    pub language: Option<String>,
+}
+
+impl ContentDisposition {
+    /// Returns the `filename` parameter, if present.
+    pub fn get_filename(&self) -> Option<&str> {
+        self.get_param("filename")
+    }
+
+    /// Returns the `name` parameter (used in `multipart/form-data` bodies), if present.
+    pub fn get_name(&self) -> Option<&str> {
+        self.get_param("name")
+    }
+
+    /// Returns an arbitrary parameter by name.
+    pub fn get_param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value.as_str())
+    }
+
+    /// Serializes this disposition back into a spec-compliant header value.
+    ///
+    /// A `filename` made only of printable ASCII is emitted as a plain
+    /// `filename="…"`, with any `"` or `\` backslash-escaped. A `filename`
+    /// containing non-ASCII or control characters is emitted as *both* an
+    /// ASCII-sanitized `filename="…"` fallback and an RFC 5987
+    /// `filename*=UTF-8''…` form, for maximum client compatibility.
+    pub fn to_header_value(&self) -> String {
+        let mut out = self.disposition_type.as_str().to_string();
+        for param in &self.params {
+            if param.name == "filename" {
+                let name = &param.value;
+                out.push_str(&format!("; filename=\"{}\"", escape_quoted(&ascii_fallback(name))));
+                if !is_ascii_token_safe(name) {
+                    out.push_str(&format!("; filename*=UTF-8''{}", percent_encode_5987(name)));
+                }
+            } else {
+                out.push_str(&format!(
+                    "; {}=\"{}\"",
+                    param.name,
+                    escape_quoted(&param.value)
+                ));
+            }
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for ContentDisposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_header_value())
+    }
 }
 
 /// Parse a Content-Disposition header value.
@@ -19,6 +119,7 @@ pub struct ContentDisposition {
 /// - `attachment; filename=example.txt`
 /// - `inline; filename="example.txt"`
 /// - `attachment; filename*=UTF-8''example%20file.txt` (RFC 5987)
+/// - `form-data; name="field"; filename="upload.txt"`
 ///
 /// Returns `None` if the header value is empty.
 pub fn parse_content_disposition(header: &str) -> Option<ContentDisposition> {
@@ -27,62 +128,301 @@ pub fn parse_content_disposition(header: &str) -> Option<ContentDisposition> {
         return None;
     }
 
-    // Split disposition type from parameters
-    let mut parts = header.splitn(2, ';');
-    let disposition_type = parts.next()?.trim().to_lowercase();
-    let params_str = parts.next().unwrap_or("");
+    // Split disposition type from parameters, respecting quoted-string
+    // boundaries so a `;` or `=` inside a quoted filename isn't mistaken
+    // for a parameter separator.
+    let top_level_params = split_params(header);
+    let mut parts = top_level_params.into_iter();
+    let disposition_type = DispositionType::parse(&parts.next()?.trim().to_lowercase());
 
     let mut filename: Option<String> = None;
     let mut filename_star: Option<String> = None;
+    let mut language: Option<String> = None;
+    let mut continuations: Vec<(u32, bool, String)> = Vec::new();
+    let mut extra_params: Vec<DispositionParam> = Vec::new();
 
     // Parse parameters
-    for param in params_str.split(';') {
+    for param in parts {
         let param = param.trim();
         if param.is_empty() {
             continue;
         }
 
-        if let Some((key, value)) = param.split_once('=') {
+        if let Some((key, value)) = split_param_kv(param) {
             let key = key.trim().to_lowercase();
             let value = value.trim();
 
+            if let Some((index, extended)) = parse_continuation_key(&key, "filename") {
+                continuations.push((index, extended, value.to_string()));
+                continue;
+            }
+
             match key.as_str() {
                 "filename" => {
-                    // Remove surrounding quotes if present
-                    filename = Some(unquote(value).to_string());
+                    filename = Some(unquote(value));
                 }
                 "filename*" => {
-                    // RFC 5987 encoded filename: encoding'language'value
+                    // RFC 5987 encoded filename: charset'language'value
                     // e.g., UTF-8''example%20file.txt
-                    if let Some(encoded_value) = value.split('\'').nth(2) {
-                        filename_star = Some(percent_decode(encoded_value));
-                    }
+                    let (decoded, lang) = decode_ext_value(value);
+                    filename_star = decoded;
+                    language = lang;
+                }
+                _ => {
+                    extra_params.push(DispositionParam {
+                        name: key,
+                        value: unquote(value),
+                    });
                 }
-                _ => {}
             }
         }
     }
 
+    // RFC 2231 continuations (filename*0, filename*1*, ...) take precedence
+    // over both `filename*` and plain `filename`, once reassembled in order.
+    // The language tag follows whichever value ends up resolved.
+    let (continued_filename, language) = match reassemble_continuation(continuations) {
+        Some((value, continued_language)) => (Some(value), continued_language),
+        None => (None, language),
+    };
+
     // RFC 6266: filename* takes precedence over filename
-    let resolved_filename = filename_star.or(filename);
+    let resolved_filename = continued_filename.or(filename_star).or(filename);
+
+    let mut params = Vec::new();
+    if let Some(fname) = resolved_filename {
+        params.push(DispositionParam {
+            name: "filename".to_string(),
+            value: fname,
+        });
+    }
+    params.extend(extra_params);
 
     Some(ContentDisposition {
         disposition_type,
-        filename: resolved_filename,
+        params,
+        language,
     })
 }
 
-/// Remove surrounding double quotes from a string.
-fn unquote(s: &str) -> &str {
-    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
-        &s[1..s.len() - 1]
+/// Checks whether `key` is an RFC 2231 continuation of `base` (e.g.
+/// `filename*0` or `filename*1*`), returning its section index and whether
+/// the section carries extended (percent-encoded) encoding.
+fn parse_continuation_key(key: &str, base: &str) -> Option<(u32, bool)> {
+    let rest = key.strip_prefix(base)?.strip_prefix('*')?;
+    let (digits, extended) = match rest.strip_suffix('*') {
+        Some(d) => (d, true),
+        None => (rest, false),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse::<u32>().ok().map(|index| (index, extended))
+}
+
+/// Reassembles RFC 2231 continuation sections into a single resolved value,
+/// returned together with the language tag declared on the first extended
+/// section, if any.
+///
+/// Sections are ordered by their numeric index; consecutive `*`-suffixed
+/// (extended) sections are percent-decoded into a shared byte buffer (so a
+/// multi-byte character split across sections decodes correctly) using the
+/// charset declared on the first extended section, while plain sections are
+/// treated as literal quoted-string values.
+fn reassemble_continuation(
+    mut sections: Vec<(u32, bool, String)>,
+) -> Option<(String, Option<String>)> {
+    if sections.is_empty() {
+        return None;
+    }
+    sections.sort_by_key(|(index, _, _)| *index);
+
+    let mut result = String::new();
+    let mut pending_bytes: Vec<u8> = Vec::new();
+    let mut charset: Option<String> = None;
+    let mut language: Option<String> = None;
+    let mut seen_first_extended = false;
+
+    for (_, extended, raw_value) in sections {
+        if extended {
+            let encoded = if !seen_first_extended {
+                seen_first_extended = true;
+                let mut fields = raw_value.splitn(3, '\'');
+                charset = fields.next().map(|s| s.to_string());
+                language = fields.next().filter(|s| !s.is_empty()).map(String::from);
+                fields.next().unwrap_or("").to_string()
+            } else {
+                raw_value
+            };
+            pending_bytes.extend(percent_decode_bytes(&encoded));
+        } else {
+            if !pending_bytes.is_empty() {
+                result.push_str(&decode_charset_bytes(
+                    &pending_bytes,
+                    charset.as_deref().unwrap_or("UTF-8"),
+                ));
+                pending_bytes.clear();
+            }
+            result.push_str(&unquote(&raw_value));
+        }
+    }
+    if !pending_bytes.is_empty() {
+        result.push_str(&decode_charset_bytes(
+            &pending_bytes,
+            charset.as_deref().unwrap_or("UTF-8"),
+        ));
+    }
+    Some((result, language))
+}
+
+/// Decodes an RFC 5987 extended value (`charset'language'value`), returning
+/// the decoded string and the language tag, if present.
+fn decode_ext_value(value: &str) -> (Option<String>, Option<String>) {
+    let mut fields = value.splitn(3, '\'');
+    let charset = fields.next().unwrap_or("UTF-8");
+    let language = fields.next().filter(|s| !s.is_empty()).map(String::from);
+    let encoded = fields.next().unwrap_or("");
+    let bytes = percent_decode_bytes(encoded);
+    (Some(decode_charset_bytes(&bytes, charset)), language)
+}
+
+/// Decodes raw bytes using the charset label from an RFC 5987/2231 value.
+/// Supports `UTF-8` and `ISO-8859-1` (Latin-1); unknown charsets gracefully
+/// fall back to lossy UTF-8 decoding.
+fn decode_charset_bytes(bytes: &[u8], charset: &str) -> String {
+    if charset.eq_ignore_ascii_case("iso-8859-1") || charset.eq_ignore_ascii_case("latin1") {
+        bytes.iter().map(|&b| b as char).collect()
     } else {
-        s
+        // UTF-8 and any unrecognized charset fall back to lossy UTF-8.
+        String::from_utf8_lossy(bytes).to_string()
+    }
+}
+
+/// Returns true if `s` can be safely carried as a plain quoted-string
+/// filename without RFC 5987 encoding: ASCII only, no control characters.
+fn is_ascii_token_safe(s: &str) -> bool {
+    s.is_ascii() && s.bytes().all(|b| (0x20..0x7f).contains(&b))
+}
+
+/// Replaces any character that isn't printable ASCII with `_`, for use as the
+/// plain `filename=` fallback alongside an RFC 5987 `filename*=` value.
+fn ascii_fallback(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Escapes `\` and `"` for use inside an HTTP quoted-string.
+fn escape_quoted(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Percent-encodes bytes not in the RFC 5987 `attr-char` set.
+fn percent_encode_5987(s: &str) -> String {
+    let mut out = String::new();
+    for byte in s.as_bytes() {
+        let b = *byte;
+        let is_attr_char = b.is_ascii_alphanumeric() || matches!(b, b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~');
+        if is_attr_char {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02X}", b));
+        }
+    }
+    out
+}
+
+/// Splits a Content-Disposition header into its top-level `;`-separated
+/// segments (disposition type plus each `key=value` parameter), treating a
+/// `;` inside a quoted-string value as literal rather than a separator.
+fn split_params(header: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut chars = header.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if escaped {
+            current.push(c);
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes => {
+                current.push(c);
+                escaped = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            ';' if !in_quotes => {
+                segments.push(current.clone());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    segments.push(current);
+    segments
+}
+
+/// Splits a single `key=value` parameter segment on its first top-level `=`,
+/// i.e. one that isn't inside a quoted-string value.
+fn split_param_kv(param: &str) -> Option<(&str, &str)> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in param.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '=' if !in_quotes => return Some((&param[..i], &param[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses an RFC 7230 quoted-string, unescaping `\x` to a literal `x`.
+/// Values without surrounding quotes, including a lone `"`, are returned
+/// unchanged. An unterminated quoted-string degrades gracefully, returning
+/// everything after the opening quote with escapes resolved.
+fn unquote(s: &str) -> String {
+    if !s.starts_with('"') || s.len() < 2 {
+        return s.to_string();
+    }
+
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s[1..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                } else {
+                    break;
+                }
+            }
+            '"' => break,
+            _ => result.push(c),
+        }
     }
+    result
 }
 
-/// Simple percent-decode for RFC 5987 encoded values.
-fn percent_decode(input: &str) -> String {
+/// Percent-decode a string into raw bytes, per RFC 3986.
+pub(crate) fn percent_decode_bytes(input: &str) -> Vec<u8> {
     let mut result = Vec::new();
     let bytes = input.as_bytes();
     let mut i = 0;
@@ -102,7 +442,7 @@ fn percent_decode(input: &str) -> String {
         i += 1;
     }
 
-    String::from_utf8_lossy(&result).to_string()
+    result
 }
 
 #[cfg(test)]
@@ -112,29 +452,29 @@ mod tests {
     #[test]
     fn test_parse_attachment_with_quoted_filename() {
         let cd = parse_content_disposition("attachment; filename=\"example.txt\"").unwrap();
-        assert_eq!(cd.disposition_type, "attachment");
-        assert_eq!(cd.filename.as_deref(), Some("example.txt"));
+        assert_eq!(cd.disposition_type, DispositionType::Attachment);
+        assert_eq!(cd.get_filename(), Some("example.txt"));
     }
 
     #[test]
     fn test_parse_attachment_with_unquoted_filename() {
         let cd = parse_content_disposition("attachment; filename=example.txt").unwrap();
-        assert_eq!(cd.disposition_type, "attachment");
-        assert_eq!(cd.filename.as_deref(), Some("example.txt"));
+        assert_eq!(cd.disposition_type, DispositionType::Attachment);
+        assert_eq!(cd.get_filename(), Some("example.txt"));
     }
 
     #[test]
     fn test_parse_inline() {
         let cd = parse_content_disposition("inline; filename=\"photo.jpg\"").unwrap();
-        assert_eq!(cd.disposition_type, "inline");
-        assert_eq!(cd.filename.as_deref(), Some("photo.jpg"));
+        assert_eq!(cd.disposition_type, DispositionType::Inline);
+        assert_eq!(cd.get_filename(), Some("photo.jpg"));
     }
 
     #[test]
     fn test_parse_no_filename() {
         let cd = parse_content_disposition("attachment").unwrap();
-        assert_eq!(cd.disposition_type, "attachment");
-        assert!(cd.filename.is_none());
+        assert_eq!(cd.disposition_type, DispositionType::Attachment);
+        assert!(cd.get_filename().is_none());
     }
 
     #[test]
@@ -146,8 +486,8 @@ mod tests {
     fn test_parse_rfc5987_filename_star() {
         let cd =
             parse_content_disposition("attachment; filename*=UTF-8''example%20file.txt").unwrap();
-        assert_eq!(cd.disposition_type, "attachment");
-        assert_eq!(cd.filename.as_deref(), Some("example file.txt"));
+        assert_eq!(cd.disposition_type, DispositionType::Attachment);
+        assert_eq!(cd.get_filename(), Some("example file.txt"));
     }
 
     #[test]
@@ -156,13 +496,81 @@ mod tests {
             "attachment; filename=\"fallback.txt\"; filename*=UTF-8''preferred.txt",
         )
         .unwrap();
-        assert_eq!(cd.filename.as_deref(), Some("preferred.txt"));
+        assert_eq!(cd.get_filename(), Some("preferred.txt"));
     }
 
     #[test]
     fn test_parse_case_insensitive_type() {
         let cd = parse_content_disposition("Attachment; filename=\"test.txt\"").unwrap();
-        assert_eq!(cd.disposition_type, "attachment");
+        assert_eq!(cd.disposition_type, DispositionType::Attachment);
+    }
+
+    #[test]
+    fn test_parse_ext_disposition_type() {
+        let cd = parse_content_disposition("render; filename=\"a.txt\"").unwrap();
+        assert_eq!(
+            cd.disposition_type,
+            DispositionType::Ext("render".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_form_data_with_name() {
+        let cd = parse_content_disposition("form-data; name=\"file\"; filename=\"upload.txt\"")
+            .unwrap();
+        assert_eq!(cd.disposition_type, DispositionType::FormData);
+        assert_eq!(cd.get_name(), Some("file"));
+        assert_eq!(cd.get_filename(), Some("upload.txt"));
+    }
+
+    #[test]
+    fn test_parse_form_data_field_only() {
+        let cd = parse_content_disposition("form-data; name=\"field1\"").unwrap();
+        assert_eq!(cd.disposition_type, DispositionType::FormData);
+        assert_eq!(cd.get_name(), Some("field1"));
+        assert!(cd.get_filename().is_none());
+    }
+
+    #[test]
+    fn test_continuation_plain_sections() {
+        let cd = parse_content_disposition(
+            "attachment; filename*0=\"part1\"; filename*1=\"part2\"",
+        )
+        .unwrap();
+        assert_eq!(cd.get_filename(), Some("part1part2"));
+    }
+
+    #[test]
+    fn test_continuation_extended_sections() {
+        let cd = parse_content_disposition(
+            "attachment; filename*0*=UTF-8''%e2%82%ac%20rates; filename*1*=.txt",
+        )
+        .unwrap();
+        assert_eq!(cd.get_filename(), Some("\u{20ac} rates.txt"));
+    }
+
+    #[test]
+    fn test_continuation_out_of_order_sections() {
+        let cd = parse_content_disposition(
+            "attachment; filename*1=\"second\"; filename*0=\"first\"",
+        )
+        .unwrap();
+        assert_eq!(cd.get_filename(), Some("firstsecond"));
+    }
+
+    #[test]
+    fn test_continuation_takes_precedence_over_plain_filename() {
+        let cd = parse_content_disposition(
+            "attachment; filename=\"fallback.txt\"; filename*0=\"split.txt\"",
+        )
+        .unwrap();
+        assert_eq!(cd.get_filename(), Some("split.txt"));
+    }
+
+    #[test]
+    fn test_get_param_extension() {
+        let cd = parse_content_disposition("attachment; filename=\"a.txt\"; size=1024").unwrap();
+        assert_eq!(cd.get_param("size"), Some("1024"));
     }
 
     #[test]
@@ -174,9 +582,107 @@ mod tests {
     }
 
     #[test]
-    fn test_percent_decode() {
-        assert_eq!(percent_decode("hello%20world"), "hello world");
-        assert_eq!(percent_decode("test%2Fpath"), "test/path");
-        assert_eq!(percent_decode("no_encoding"), "no_encoding");
+    fn test_unquote_escaped_quote() {
+        assert_eq!(unquote("\"my \\\"report\\\".txt\""), "my \"report\".txt");
+    }
+
+    #[test]
+    fn test_unquote_escaped_backslash() {
+        assert_eq!(unquote("\"path\\\\to\\\\file\""), "path\\to\\file");
+    }
+
+    #[test]
+    fn test_unquote_unterminated_quote_degrades_gracefully() {
+        assert_eq!(unquote("\"unterminated"), "unterminated");
+    }
+
+    #[test]
+    fn test_parse_filename_with_escaped_quote() {
+        let cd =
+            parse_content_disposition("attachment; filename=\"my \\\"report\\\".txt\"").unwrap();
+        assert_eq!(cd.get_filename(), Some("my \"report\".txt"));
+    }
+
+    #[test]
+    fn test_parse_semicolon_inside_quoted_filename() {
+        let cd = parse_content_disposition(
+            "attachment; filename=\"a; b.txt\"; name=\"field\"",
+        )
+        .unwrap();
+        assert_eq!(cd.get_filename(), Some("a; b.txt"));
+        assert_eq!(cd.get_name(), Some("field"));
+    }
+
+    #[test]
+    fn test_parse_equals_inside_quoted_filename() {
+        let cd = parse_content_disposition("attachment; filename=\"a=b.txt\"").unwrap();
+        assert_eq!(cd.get_filename(), Some("a=b.txt"));
+    }
+
+    #[test]
+    fn test_percent_decode_bytes() {
+        assert_eq!(percent_decode_bytes("hello%20world"), b"hello world");
+        assert_eq!(percent_decode_bytes("test%2Fpath"), b"test/path");
+        assert_eq!(percent_decode_bytes("no_encoding"), b"no_encoding");
+    }
+
+    #[test]
+    fn test_parse_rfc5987_latin1_charset() {
+        let cd = parse_content_disposition("attachment; filename*=ISO-8859-1''caf%e9.txt")
+            .unwrap();
+        assert_eq!(cd.get_filename(), Some("caf\u{e9}.txt"));
+    }
+
+    #[test]
+    fn test_parse_rfc5987_language_tag() {
+        let cd = parse_content_disposition("attachment; filename*=UTF-8'en'doc.txt").unwrap();
+        assert_eq!(cd.get_filename(), Some("doc.txt"));
+        assert_eq!(cd.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_parse_rfc5987_unknown_charset_falls_back() {
+        let cd = parse_content_disposition("attachment; filename*=UTF-16''abc").unwrap();
+        assert_eq!(cd.get_filename(), Some("abc"));
+    }
+
+    #[test]
+    fn test_to_header_value_ascii_filename() {
+        let cd = parse_content_disposition("attachment; filename=\"report.pdf\"").unwrap();
+        assert_eq!(cd.to_header_value(), "attachment; filename=\"report.pdf\"");
+    }
+
+    #[test]
+    fn test_to_header_value_non_ascii_filename_emits_both_forms() {
+        let cd = parse_content_disposition("attachment; filename*=UTF-8''caf%c3%a9.txt").unwrap();
+        let header = cd.to_header_value();
+        assert!(header.starts_with("attachment; filename=\"caf_.txt\""));
+        assert!(header.contains("filename*=UTF-8''caf%C3%A9.txt"));
+    }
+
+    #[test]
+    fn test_to_header_value_roundtrip_ascii() {
+        let original = "attachment; filename=\"example.txt\"";
+        let cd = parse_content_disposition(original).unwrap();
+        let reparsed = parse_content_disposition(&cd.to_header_value()).unwrap();
+        assert_eq!(cd.get_filename(), reparsed.get_filename());
+    }
+
+    #[test]
+    fn test_to_header_value_roundtrip_non_ascii() {
+        let cd = parse_content_disposition("attachment; filename*=UTF-8''%e2%82%ac.txt").unwrap();
+        let reparsed = parse_content_disposition(&cd.to_header_value()).unwrap();
+        assert_eq!(cd.get_filename(), reparsed.get_filename());
+    }
+
+    #[test]
+    fn test_escape_quoted() {
+        assert_eq!(escape_quoted("my \"report\".txt"), "my \\\"report\\\".txt");
+    }
+
+    #[test]
+    fn test_display_matches_to_header_value() {
+        let cd = parse_content_disposition("inline; filename=\"photo.jpg\"").unwrap();
+        assert_eq!(cd.to_string(), cd.to_header_value());
     }
 }
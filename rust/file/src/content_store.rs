@@ -0,0 +1,117 @@
+//! Content-addressed lookup for files saved via [`crate::file::File::save_by_checksum`].
+
+use std::path::Path;
+
+use crate::error::{FileError, Result};
+use crate::file::File;
+use crate::source::FileSource;
+
+/// Resolves files previously written by [`crate::file::File::save_by_checksum`]
+/// back to a [`File`] by their checksum, without needing to know the
+/// extension it was stored with.
+pub struct ContentStore;
+
+impl ContentStore {
+    /// Find and load the file stored under `checksum` in `dir`.
+    ///
+    /// `checksum` must be a 64-character hex SHA-256 digest (the same format
+    /// [`crate::file::File::content_address`] produces). The directory is
+    /// scanned for an entry named `{checksum}` or `{checksum}.{ext}`, since
+    /// [`crate::file::File::save_by_checksum`] doesn't fix a particular
+    /// extension. Returns a [`File`] with [`FileSource::File`].
+    pub async fn get(dir: &str, checksum: &str) -> Result<File> {
+        if checksum.len() != 64 || !checksum.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(FileError::InvalidSource(format!(
+                "'{}' is not a 64-character hex checksum",
+                checksum
+            )));
+        }
+
+        let prefix = format!("{}.", checksum);
+        let mut entries = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            if name == checksum || name.starts_with(&prefix) {
+                let path = Path::new(dir).join(name);
+                let path = path.to_str().ok_or_else(|| {
+                    FileError::InvalidSource(format!("'{}' is not valid UTF-8", path.display()))
+                })?;
+                let file = File::from_file(path, None).await?;
+                debug_assert_eq!(file.source(), FileSource::File);
+                return Ok(file);
+            }
+        }
+
+        Err(FileError::InvalidSource(format!(
+            "no file with checksum '{}' found in '{}'",
+            checksum, dir
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[tokio::test]
+    async fn test_get_resolves_stored_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-content-store-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+        let (_, saved) = file
+            .save_by_checksum(dir.to_str().unwrap())
+            .await
+            .unwrap();
+        let checksum = saved
+            .path()
+            .unwrap()
+            .rsplit('/')
+            .next()
+            .unwrap()
+            .split('.')
+            .next()
+            .unwrap()
+            .to_string();
+
+        let found = ContentStore::get(dir.to_str().unwrap(), &checksum)
+            .await
+            .unwrap();
+        assert_eq!(found.read_text().await.unwrap(), "hello world");
+        assert_eq!(found.source(), FileSource::File);
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_rejects_invalid_checksum_format() {
+        let err = ContentStore::get("/tmp", "not-a-checksum").await;
+        assert!(err.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_errors_when_not_found() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-content-store-missing-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let checksum = "a".repeat(64);
+        let err = ContentStore::get(dir.to_str().unwrap(), &checksum).await;
+        assert!(err.is_err());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+//! `data:` URL parsing per RFC 2397.
+
+use base64::Engine;
+
+use crate::content_disposition::percent_decode_bytes;
+use crate::detection::{detect_from_bytes, extension_from_mime};
+use crate::error::{FileError, Result};
+use crate::metadata::Metadata;
+
+/// A parsed `data:` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataUrl {
+    /// The media type (e.g. "image/png"), defaulting to
+    /// "text/plain;charset=US-ASCII" when omitted.
+    pub media_type: String,
+    /// The `charset` parameter on the media type, if present.
+    pub charset: Option<String>,
+    /// The decoded payload bytes.
+    pub data: Vec<u8>,
+}
+
+/// Parses a `data:[<mediatype>][;base64],<data>` URL per RFC 2397.
+///
+/// The media type and `;charset=`/`;base64` attributes are parsed from the
+/// prefix before the first comma. When `;base64` is present the payload is
+/// base64-decoded; otherwise it is percent-decoded.
+pub fn parse_data_url(uri: &str) -> Result<DataUrl> {
+    let rest = uri
+        .strip_prefix("data:")
+        .ok_or_else(|| FileError::DataUrl(format!("missing data: scheme: {}", uri)))?;
+
+    let (header, payload) = rest
+        .split_once(',')
+        .ok_or_else(|| FileError::DataUrl(format!("missing comma separator: {}", uri)))?;
+
+    let mut is_base64 = false;
+    let mut charset = None;
+    let mut media_type = String::new();
+
+    for (i, segment) in header.split(';').enumerate() {
+        if i == 0 {
+            media_type = segment.to_string();
+            continue;
+        }
+        if segment.eq_ignore_ascii_case("base64") {
+            is_base64 = true;
+        } else if let Some(value) = segment.strip_prefix("charset=") {
+            charset = Some(value.to_string());
+        }
+    }
+
+    if media_type.is_empty() {
+        // RFC 2397's default when no media type is given.
+        media_type = "text/plain;charset=US-ASCII".to_string();
+        charset = Some("US-ASCII".to_string());
+    }
+
+    let data = if is_base64 {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| FileError::DataUrl(format!("invalid base64 payload: {}", e)))?
+    } else {
+        percent_decode_bytes(payload)
+    };
+
+    Ok(DataUrl {
+        media_type,
+        charset,
+        data,
+    })
+}
+
+/// Decode a `data:` URL into its payload bytes and the `Metadata` it implies.
+///
+/// The declared media type seeds `mime_type`, but the decoded bytes are then
+/// run through [`detect_from_bytes`]; a confident detection result overrides
+/// the declared type so a mislabeled data URL (e.g. `data:text/plain,...`
+/// actually holding a PNG) still gets corrected. `extension` is derived from
+/// whichever MIME type wins.
+pub fn from_data_url(uri: &str) -> Result<(Vec<u8>, Metadata)> {
+    let parsed = parse_data_url(uri)?;
+    let detection = detect_from_bytes(&parsed.data, None);
+
+    let mime_type = detection.mime_type.unwrap_or(parsed.media_type);
+    let extension = detection
+        .extension
+        .or_else(|| extension_from_mime(&mime_type));
+
+    let metadata = Metadata {
+        mime_type: Some(mime_type),
+        extension,
+        size: Some(parsed.data.len() as u64),
+        ..Default::default()
+    };
+
+    Ok((parsed.data, metadata))
+}
+
+/// Encode `bytes` as a `data:[<mime>];base64,<data>` URL.
+///
+/// When `mime` is omitted, it is inferred from the bytes via
+/// [`detect_from_bytes`], falling back to `application/octet-stream`.
+pub fn to_data_url(bytes: &[u8], mime: Option<&str>) -> String {
+    let mime = mime.map(|m| m.to_string()).unwrap_or_else(|| {
+        detect_from_bytes(bytes, None)
+            .mime_type
+            .unwrap_or_else(|| "application/octet-stream".to_string())
+    });
+    let encoded = base64::engine::general_purpose::STANDARD.encode(bytes);
+    format!("data:{};base64,{}", mime, encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_text_data_url() {
+        let parsed = parse_data_url("data:,hello%20world").unwrap();
+        assert_eq!(parsed.media_type, "text/plain;charset=US-ASCII");
+        assert_eq!(parsed.charset.as_deref(), Some("US-ASCII"));
+        assert_eq!(parsed.data, b"hello world");
+    }
+
+    #[test]
+    fn test_parse_base64_data_url() {
+        let parsed = parse_data_url("data:image/png;base64,aGVsbG8=").unwrap();
+        assert_eq!(parsed.media_type, "image/png");
+        assert_eq!(parsed.data, b"hello");
+    }
+
+    #[test]
+    fn test_parse_data_url_with_charset() {
+        let parsed = parse_data_url("data:text/plain;charset=UTF-8,hi").unwrap();
+        assert_eq!(parsed.media_type, "text/plain");
+        assert_eq!(parsed.charset.as_deref(), Some("UTF-8"));
+        assert_eq!(parsed.data, b"hi");
+    }
+
+    #[test]
+    fn test_parse_data_url_missing_scheme() {
+        assert!(parse_data_url("not-a-data-url").is_err());
+    }
+
+    #[test]
+    fn test_parse_data_url_missing_comma() {
+        assert!(parse_data_url("data:text/plain").is_err());
+    }
+
+    #[test]
+    fn test_parse_data_url_invalid_base64() {
+        assert!(parse_data_url("data:;base64,not valid base64!!").is_err());
+    }
+
+    #[test]
+    fn test_from_data_url_corrects_mislabeled_mime() {
+        let png_b64 = base64::engine::general_purpose::STANDARD
+            .encode([0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]);
+        let uri = format!("data:text/plain;base64,{}", png_b64);
+
+        let (data, metadata) = from_data_url(&uri).unwrap();
+        assert_eq!(data.len(), 8);
+        assert_eq!(metadata.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(metadata.extension.as_deref(), Some("png"));
+    }
+
+    #[test]
+    fn test_from_data_url_keeps_declared_mime_when_undetectable() {
+        let uri = "data:text/plain,hello";
+        let (data, metadata) = from_data_url(uri).unwrap();
+        assert_eq!(data, b"hello");
+        assert_eq!(metadata.mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn test_to_data_url_roundtrips_with_explicit_mime() {
+        let uri = to_data_url(b"hello", Some("text/plain"));
+        assert_eq!(uri, "data:text/plain;base64,aGVsbG8=");
+
+        let (data, metadata) = from_data_url(&uri).unwrap();
+        assert_eq!(data, b"hello");
+        assert_eq!(metadata.mime_type.as_deref(), Some("text/plain"));
+    }
+
+    #[test]
+    fn test_to_data_url_infers_mime_when_omitted() {
+        let png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let uri = to_data_url(&png, None);
+        assert!(uri.starts_with("data:image/png;base64,"));
+    }
+}
@@ -4,6 +4,15 @@
 //! 1. `infer` crate - magic byte detection for binary formats
 //! 2. Custom SVG/XML detection for text-based XML formats
 //! 3. `mime_guess` crate - extension-based fallback
+//!
+//! The strategy above is hard-wired into the free functions
+//! ([`detect_from_bytes`], [`detect_from_filename`]) via the default
+//! [`InferBackend`]. Callers that want a different registry (e.g. the host
+//! system's shared-mime-info database, which knows far more types and glob
+//! rules than `infer`/`mime_guess` combined) can implement [`MimeDb`] and
+//! drive detection through [`detect_from_bytes_with`]/
+//! [`detect_from_filename_with`] instead. The SVG/XML custom detector always
+//! runs as a shared post-processing step regardless of which backend is used.
 
 /// Result of a detection attempt.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,35 +23,166 @@ pub struct DetectionResult {
     pub extension: Option<String>,
 }
 
+impl DetectionResult {
+    /// Parse `mime_type` into a [`mime::Mime`], exposing its top-level type
+    /// (`image`), subtype (`svg+xml`), suffix, and parameters (e.g.
+    /// `charset`) without callers having to re-parse the string themselves.
+    pub fn mime(&self) -> Option<mime::Mime> {
+        self.mime_type.as_deref()?.parse().ok()
+    }
+}
+
+/// A pluggable source of MIME type knowledge, analogous to `fif`'s choice
+/// between an `infer`-backed database and an `xdg-mime`-backed one.
+///
+/// Implementations only need to answer "what do the magic bytes/filename
+/// look like" — the SVG/XML refinement and any other shared post-processing
+/// stays in [`detect_from_bytes_with`], so every backend benefits from it
+/// uniformly.
+pub trait MimeDb {
+    /// Detect a MIME type and extension from magic bytes alone.
+    fn detect_from_bytes(&self, bytes: &[u8]) -> DetectionResult;
+    /// Detect a MIME type and extension from a filename alone.
+    fn detect_from_filename(&self, filename: &str) -> DetectionResult;
+}
+
+/// The default [`MimeDb`]: `infer`'s magic byte signatures for content,
+/// `mime_guess`'s extension table for filenames. This is the backend used by
+/// [`detect_from_bytes`]/[`detect_from_filename`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InferBackend;
+
+impl MimeDb for InferBackend {
+    fn detect_from_bytes(&self, bytes: &[u8]) -> DetectionResult {
+        match infer::get(bytes) {
+            Some(kind) => DetectionResult {
+                mime_type: Some(kind.mime_type().to_string()),
+                extension: Some(kind.extension().to_string()),
+            },
+            None => DetectionResult {
+                mime_type: None,
+                extension: None,
+            },
+        }
+    }
+
+    fn detect_from_filename(&self, filename: &str) -> DetectionResult {
+        let guess = mime_guess::from_path(filename);
+        let mime_type = guess.first().map(|m| m.to_string());
+        let extension = std::path::Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_string());
+
+        DetectionResult {
+            mime_type,
+            extension,
+        }
+    }
+}
+
+/// Queries the host's shared-mime-info database (the same registry `file(1)`,
+/// `xdg-mime`, and most desktop environments use on Linux) instead of the
+/// bundled `infer`/`mime_guess` tables, so applications get the much larger
+/// system type list and glob rules.
+///
+/// Gated behind the `shared-mime-info` feature since it depends on a
+/// system-provided database rather than data bundled into the binary.
+#[cfg(feature = "shared-mime-info")]
+#[derive(Debug, Clone, Default)]
+pub struct SharedMimeInfoBackend {
+    db: xdg_mime::SharedMimeInfo,
+}
+
+#[cfg(feature = "shared-mime-info")]
+impl SharedMimeInfoBackend {
+    /// Load the system's shared-mime-info database.
+    pub fn new() -> Self {
+        Self {
+            db: xdg_mime::SharedMimeInfo::new(),
+        }
+    }
+}
+
+#[cfg(feature = "shared-mime-info")]
+impl MimeDb for SharedMimeInfoBackend {
+    fn detect_from_bytes(&self, bytes: &[u8]) -> DetectionResult {
+        let guess = self.db.get_mime_types_from_data(bytes);
+        let mime_type = guess.first().map(|m| m.to_string());
+        let extension = mime_type
+            .as_deref()
+            .and_then(mime_guess::get_mime_extensions_str)
+            .and_then(|exts| exts.first().copied())
+            .map(|e| e.to_string());
+
+        DetectionResult {
+            mime_type,
+            extension,
+        }
+    }
+
+    fn detect_from_filename(&self, filename: &str) -> DetectionResult {
+        let guess = self.db.get_mime_types_from_file_name(filename);
+        let mime_type = guess.first().map(|m| m.to_string());
+        let extension = std::path::Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_string());
+
+        DetectionResult {
+            mime_type,
+            extension,
+        }
+    }
+}
+
 /// Detect MIME type and extension from raw bytes using magic byte signatures.
 ///
 /// This uses the `infer` crate first, then falls back to custom SVG/XML detection,
 /// and finally uses `mime_guess` from a filename if provided.
+///
+/// Equivalent to [`detect_from_bytes_with`] with the default [`InferBackend`].
 pub fn detect_from_bytes(bytes: &[u8], filename: Option<&str>) -> DetectionResult {
-    // Strategy 1: infer crate magic bytes
-    if let Some(kind) = infer::get(bytes) {
-        let mime = kind.mime_type();
-        // If infer detected XML-like content, use our custom SVG/XML detector
-        // which can distinguish SVG from generic XML and provide better types.
+    detect_from_bytes_with(&InferBackend, bytes, filename)
+}
+
+/// Detect MIME type and extension from raw bytes using a caller-supplied
+/// [`MimeDb`] backend.
+///
+/// Strategy, in priority order:
+/// 1. `db.detect_from_bytes` - the backend's own magic byte detection
+/// 2. Custom SVG/XML detection - always applied, regardless of backend, so
+///    SVG is distinguished from generic XML and `text/xml`/`application/xml`
+///    results are refined
+/// 3. `db.detect_from_filename` - the backend's filename-based fallback, if
+///    a filename was given
+pub fn detect_from_bytes_with(
+    db: &dyn MimeDb,
+    bytes: &[u8],
+    filename: Option<&str>,
+) -> DetectionResult {
+    // Strategy 1: backend magic bytes
+    let result = db.detect_from_bytes(bytes);
+    if let Some(mime) = &result.mime_type {
+        // If the backend detected XML-like content, use our custom SVG/XML
+        // detector which can distinguish SVG from generic XML and provide
+        // better types. This runs for every backend, not just `InferBackend`.
         if mime == "text/xml" || mime == "application/xml" {
-            if let Some(result) = detect_svg_xml(bytes) {
-                return result;
+            if let Some(refined) = detect_svg_xml(bytes) {
+                return refined;
             }
         }
-        return DetectionResult {
-            mime_type: Some(mime.to_string()),
-            extension: Some(kind.extension().to_string()),
-        };
+        return result;
     }
 
-    // Strategy 2: custom SVG/XML detection (for content infer doesn't recognize)
+    // Strategy 2: custom SVG/XML detection (for content the backend doesn't recognize)
     if let Some(result) = detect_svg_xml(bytes) {
         return result;
     }
 
-    // Strategy 3: mime_guess from filename
+    // Strategy 3: backend's filename-based fallback
     if let Some(name) = filename {
-        return detect_from_filename(name);
+        return db.detect_from_filename(name);
     }
 
     DetectionResult {
@@ -52,17 +192,190 @@ pub fn detect_from_bytes(bytes: &[u8], filename: Option<&str>) -> DetectionResul
 }
 
 /// Detect MIME type and extension from a filename using the `mime_guess` crate.
+///
+/// Equivalent to [`detect_from_filename_with`] with the default [`InferBackend`].
 pub fn detect_from_filename(filename: &str) -> DetectionResult {
-    let guess = mime_guess::from_path(filename);
-    let mime_type = guess.first().map(|m| m.to_string());
-    let extension = std::path::Path::new(filename)
+    InferBackend.detect_from_filename(filename)
+}
+
+/// Detect MIME type and extension from a filename using a caller-supplied
+/// [`MimeDb`] backend.
+pub fn detect_from_filename_with(db: &dyn MimeDb, filename: &str) -> DetectionResult {
+    db.detect_from_filename(filename)
+}
+
+/// The default prefix size [`detect_streaming`] reads before giving up,
+/// chosen to comfortably cover every magic-byte signature `infer` looks for
+/// plus a generous slice of leading text for the SVG/XML sniff.
+pub const DEFAULT_STREAM_SCAN_BYTES: usize = 8192;
+
+/// Options controlling how much of a stream [`detect_streaming`] reads and
+/// which matcher tiers it's allowed to run.
+#[derive(Debug, Clone, Copy)]
+pub struct DetectOptions {
+    /// Maximum number of bytes to read from the stream before detecting.
+    /// `FileSource::Stream`/`Url`/`S3` callers use this to classify a file
+    /// without buffering its entire body.
+    pub max_bytes: usize,
+    /// Whether to run the expensive "slow" content-sniffing matchers (today:
+    /// the SVG/XML text scan) in addition to the cheap "fast" ones. When
+    /// `false`, only magic-byte and filename/extension matching run.
+    pub allow_slow: bool,
+}
+
+impl Default for DetectOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_STREAM_SCAN_BYTES,
+            allow_slow: true,
+        }
+    }
+}
+
+/// The outcome of [`detect_streaming`]: the winning result plus the cheap
+/// "fast" guess that was computed along the way, so callers can see when the
+/// two disagreed instead of only getting the final verdict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamDetection {
+    /// The final detection result. When the fast and slow tiers disagree,
+    /// this is the slow (content-sniffed) result, since it's based on more
+    /// evidence.
+    pub result: DetectionResult,
+    /// The cheap fast-tier guess (leading magic bytes, or filename/extension
+    /// as a fallback), if either produced one.
+    pub fast_guess: Option<DetectionResult>,
+}
+
+/// Classify a stream by reading only up to `opts.max_bytes` from it, the way
+/// ripgrep-all separates cheap `fast_matchers` from expensive `slow_matchers`
+/// and lets the slow pass override the fast one.
+///
+/// Fast matchers: leading magic bytes (via [`InferBackend`], which only ever
+/// inspects a short header) and filename/extension globs -- both effectively
+/// free no matter how large `max_bytes` is. Slow matchers: the SVG/XML
+/// content sniff, run over at most the first 4096 bytes of the buffered
+/// prefix; this is what
+/// `allow_slow = false` skips. The SVG/XML sniff tolerates a multibyte
+/// character split at the `max_bytes` boundary (see [`utf8_prefix`]), so a
+/// truncated read can't corrupt the result.
+///
+/// This never reads more than `opts.max_bytes`, so `FileSource::Stream`/
+/// `Url`/`S3` sources can be classified from a bounded prefix without
+/// buffering the whole payload.
+pub fn detect_streaming<R: std::io::Read>(
+    mut reader: R,
+    opts: DetectOptions,
+    filename: Option<&str>,
+) -> StreamDetection {
+    let mut buf = vec![0u8; opts.max_bytes];
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(_) => break,
+        }
+    }
+    buf.truncate(filled);
+
+    // Fast tier: leading magic bytes, then filename/extension as a fallback.
+    let magic = InferBackend.detect_from_bytes(&buf);
+    let by_name = filename
+        .map(detect_from_filename)
+        .filter(|r| r.mime_type.is_some());
+    let fast_guess = if magic.mime_type.is_some() {
+        Some(magic.clone())
+    } else {
+        by_name
+    };
+
+    if !opts.allow_slow {
+        let result = fast_guess
+            .clone()
+            .unwrap_or(DetectionResult { mime_type: None, extension: None });
+        return StreamDetection { result, fast_guess };
+    }
+
+    // Slow tier: full content sniff. Only worth running when the fast tier
+    // came back empty, or flagged the generic "might be XML" case that the
+    // sniff can refine into SVG/XML/HTML.
+    let is_xml_like = matches!(
+        magic.mime_type.as_deref(),
+        Some("text/xml") | Some("application/xml")
+    );
+    let slow_result = if magic.mime_type.is_none() || is_xml_like {
+        detect_svg_xml(&buf)
+    } else {
+        None
+    };
+
+    let result = slow_result
+        .or_else(|| fast_guess.clone())
+        .unwrap_or(DetectionResult { mime_type: None, extension: None });
+
+    StreamDetection { result, fast_guess }
+}
+
+/// The result of comparing a file's declared extension against its
+/// magic-byte-detected content type, as returned by [`verify_extension`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionVerdict {
+    /// The filename's final extension (e.g. `"gz"` for `archive.tar.gz`), if any.
+    pub original_extension: Option<String>,
+    /// The MIME type detected from the file's bytes, if the content was recognized.
+    pub detected_mime_type: Option<String>,
+    /// Every extension `mime_guess` considers valid for `detected_mime_type`.
+    pub acceptable_extensions: Vec<String>,
+    /// An extension to rename the file to, set only when `original_extension`
+    /// is present but isn't one of `acceptable_extensions`.
+    pub recommended_extension: Option<String>,
+}
+
+/// Compare `filename`'s declared extension against the MIME type detected
+/// from `bytes`' magic bytes, recommending a corrected extension on mismatch.
+///
+/// Only the bytes are used for detection (not the filename), so a true
+/// mismatch can actually be observed. Multi-dot names like `archive.tar.gz`
+/// are handled by considering only the final extension. An unrecognized or
+/// `application/octet-stream` content type carries "no opinion": it never
+/// mismatches and never recommends a rename.
+pub fn verify_extension(bytes: &[u8], filename: &str) -> ExtensionVerdict {
+    let original_extension = std::path::Path::new(filename)
         .extension()
         .and_then(|e| e.to_str())
         .map(|e| e.to_string());
 
-    DetectionResult {
-        mime_type,
-        extension,
+    let detection = detect_from_bytes(bytes, None);
+    let detected_mime_type = detection
+        .mime_type
+        .filter(|mime| mime != "application/octet-stream");
+
+    let acceptable_extensions: Vec<String> = detected_mime_type
+        .as_deref()
+        .and_then(mime_guess::get_mime_extensions_str)
+        .map(|exts| exts.iter().map(|e| e.to_string()).collect())
+        .unwrap_or_default();
+
+    let recommended_extension = match (&detected_mime_type, &original_extension) {
+        (None, _) => None,
+        (Some(_), None) => acceptable_extensions.first().cloned(),
+        (Some(_), Some(original)) => {
+            if acceptable_extensions
+                .iter()
+                .any(|ext| ext.eq_ignore_ascii_case(original))
+            {
+                None
+            } else {
+                acceptable_extensions.first().cloned()
+            }
+        }
+    };
+
+    ExtensionVerdict {
+        original_extension,
+        detected_mime_type,
+        acceptable_extensions,
+        recommended_extension,
     }
 }
 
@@ -79,20 +392,28 @@ pub fn extension_from_mime(mime: &str) -> Option<String> {
     extensions.and_then(|exts| exts.first().copied()).map(|e| e.to_string())
 }
 
+/// Returns the longest valid UTF-8 prefix of `bytes`.
+///
+/// Unlike a bare `std::str::from_utf8`, this tolerates a multibyte sequence
+/// that's truncated at the very end of `bytes` (as happens whenever a
+/// byte-limited prefix, e.g. from [`detect_streaming`], splits a character in
+/// half) by trimming back to the last complete character instead of failing
+/// outright. A genuinely invalid byte earlier in the slice still truncates
+/// the prefix there, same as before.
+fn utf8_prefix(bytes: &[u8]) -> Option<&str> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Some(s),
+        Err(e) if e.valid_up_to() > 0 => std::str::from_utf8(&bytes[..e.valid_up_to()]).ok(),
+        Err(_) => None,
+    }
+}
+
 /// Custom detection for SVG and XML content by inspecting the byte content.
 fn detect_svg_xml(bytes: &[u8]) -> Option<DetectionResult> {
-    // We need to look at the text content for SVG/XML
-    let text = match std::str::from_utf8(bytes) {
-        Ok(s) => s,
-        Err(_) => {
-            // Try a smaller prefix - some files may have valid UTF-8 at the start
-            let len = bytes.len().min(4096);
-            match std::str::from_utf8(&bytes[..len]) {
-                Ok(s) => s,
-                Err(_) => return None,
-            }
-        }
-    };
+    // We need to look at the text content for SVG/XML. A prefix is capped so
+    // we don't pay to decode arbitrarily large non-text payloads.
+    let len = bytes.len().min(4096);
+    let text = utf8_prefix(&bytes[..len])?;
 
     let trimmed = text.trim_start();
 
@@ -259,4 +580,236 @@ mod tests {
         let ext = extension_from_mime("application/x-totally-unknown-thing");
         assert!(ext.is_none());
     }
+
+    #[test]
+    fn test_verify_extension_detects_mismatch() {
+        let png_bytes = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let verdict = verify_extension(&png_bytes, "photo.txt");
+        assert_eq!(verdict.original_extension.as_deref(), Some("txt"));
+        assert_eq!(verdict.detected_mime_type.as_deref(), Some("image/png"));
+        assert!(verdict.acceptable_extensions.contains(&"png".to_string()));
+        assert_eq!(verdict.recommended_extension.as_deref(), Some("png"));
+    }
+
+    #[test]
+    fn test_verify_extension_matching_extension_no_recommendation() {
+        let png_bytes = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let verdict = verify_extension(&png_bytes, "photo.png");
+        assert!(verdict.recommended_extension.is_none());
+    }
+
+    #[test]
+    fn test_verify_extension_keeps_existing_alias_among_valid_extensions() {
+        let mut jpeg_bytes = vec![0xFF, 0xD8, 0xFF, 0xE0];
+        jpeg_bytes.extend_from_slice(&[0; 100]);
+        // "jpeg" is a valid alternate extension for image/jpeg alongside "jpg",
+        // so it should be kept rather than forced to the first-listed one.
+        let verdict = verify_extension(&jpeg_bytes, "photo.jpeg");
+        assert!(verdict.acceptable_extensions.contains(&"jpeg".to_string()));
+        assert_eq!(verdict.recommended_extension, None);
+    }
+
+    #[test]
+    fn test_verify_extension_multi_dot_name_uses_final_extension() {
+        let mut gzip_bytes = vec![0x1f, 0x8b, 0x08, 0x00];
+        gzip_bytes.extend_from_slice(&[0; 20]);
+        let verdict = verify_extension(&gzip_bytes, "archive.tar.gz");
+        assert_eq!(verdict.original_extension.as_deref(), Some("gz"));
+        assert_eq!(verdict.recommended_extension, None);
+    }
+
+    #[test]
+    fn test_verify_extension_unknown_content_is_no_opinion() {
+        let verdict = verify_extension(b"just some random text", "data.bin");
+        assert!(verdict.detected_mime_type.is_none());
+        assert!(verdict.acceptable_extensions.is_empty());
+        assert!(verdict.recommended_extension.is_none());
+    }
+
+    #[test]
+    fn test_detection_result_mime_parses_type_and_subtype() {
+        let result = DetectionResult {
+            mime_type: Some("image/svg+xml".to_string()),
+            extension: Some("svg".to_string()),
+        };
+        let mime = result.mime().unwrap();
+        assert_eq!(mime.type_(), mime::IMAGE);
+        assert_eq!(mime.subtype(), "svg");
+        assert_eq!(mime.suffix().map(|s| s.as_str()), Some("xml"));
+    }
+
+    #[test]
+    fn test_detection_result_mime_none_when_unset() {
+        let result = DetectionResult {
+            mime_type: None,
+            extension: None,
+        };
+        assert!(result.mime().is_none());
+    }
+
+    #[test]
+    fn test_verify_extension_no_extension_recommends_one() {
+        let png_bytes = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let verdict = verify_extension(&png_bytes, "photo");
+        assert!(verdict.original_extension.is_none());
+        assert_eq!(verdict.recommended_extension.as_deref(), Some("png"));
+    }
+
+    /// A stub [`MimeDb`] that always claims bytes are a custom format, so
+    /// tests can tell the injected backend's result apart from `InferBackend`'s.
+    struct StubBackend;
+
+    impl MimeDb for StubBackend {
+        fn detect_from_bytes(&self, _bytes: &[u8]) -> DetectionResult {
+            DetectionResult {
+                mime_type: Some("application/x-stub".to_string()),
+                extension: Some("stub".to_string()),
+            }
+        }
+
+        fn detect_from_filename(&self, _filename: &str) -> DetectionResult {
+            DetectionResult {
+                mime_type: Some("application/x-stub-by-name".to_string()),
+                extension: Some("stub".to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_from_bytes_with_uses_injected_backend() {
+        let png_bytes = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let result = detect_from_bytes_with(&StubBackend, &png_bytes, None);
+        assert_eq!(result.mime_type.as_deref(), Some("application/x-stub"));
+    }
+
+    #[test]
+    fn test_detect_from_bytes_with_prefers_backend_bytes_over_filename() {
+        let result = detect_from_bytes_with(&StubBackend, b"", Some("ignored.bin"));
+        assert_eq!(result.mime_type.as_deref(), Some("application/x-stub"));
+    }
+
+    #[test]
+    fn test_detect_from_filename_with_uses_injected_backend() {
+        let result = detect_from_filename_with(&StubBackend, "anything.txt");
+        assert_eq!(result.mime_type.as_deref(), Some("application/x-stub-by-name"));
+    }
+
+    #[test]
+    fn test_detect_from_bytes_with_still_refines_svg_regardless_of_backend() {
+        // A backend that (like `infer`) only recognizes generic XML should
+        // still have its result refined to SVG by the shared post-processing
+        // step, not just when using `InferBackend`.
+        struct GenericXmlBackend;
+        impl MimeDb for GenericXmlBackend {
+            fn detect_from_bytes(&self, _bytes: &[u8]) -> DetectionResult {
+                DetectionResult {
+                    mime_type: Some("text/xml".to_string()),
+                    extension: Some("xml".to_string()),
+                }
+            }
+            fn detect_from_filename(&self, _filename: &str) -> DetectionResult {
+                DetectionResult {
+                    mime_type: None,
+                    extension: None,
+                }
+            }
+        }
+
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"><rect/></svg>";
+        let result = detect_from_bytes_with(&GenericXmlBackend, svg, None);
+        assert_eq!(result.mime_type.as_deref(), Some("image/svg+xml"));
+    }
+
+    #[test]
+    fn test_infer_backend_matches_default_detect_from_bytes() {
+        let png_bytes = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        let via_default = detect_from_bytes(&png_bytes, None);
+        let via_backend = InferBackend.detect_from_bytes(&png_bytes);
+        assert_eq!(via_default, via_backend);
+    }
+
+    #[test]
+    fn test_detect_streaming_magic_bytes() {
+        let mut png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        png.extend_from_slice(&[0; 100]);
+        let detection = detect_streaming(png.as_slice(), DetectOptions::default(), None);
+        assert_eq!(detection.result.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(detection.fast_guess, Some(detection.result.clone()));
+    }
+
+    #[test]
+    fn test_detect_streaming_svg_via_slow_tier() {
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"><rect/></svg>";
+        let detection = detect_streaming(svg.as_slice(), DetectOptions::default(), None);
+        assert_eq!(detection.result.mime_type.as_deref(), Some("image/svg+xml"));
+        // `infer` has no magic bytes for plain-text SVG, so the fast tier has
+        // no opinion here -- only the slow content sniff found it.
+        assert!(detection.fast_guess.is_none());
+    }
+
+    #[test]
+    fn test_detect_streaming_allow_slow_false_skips_content_sniff() {
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"><rect/></svg>";
+        let opts = DetectOptions {
+            max_bytes: DEFAULT_STREAM_SCAN_BYTES,
+            allow_slow: false,
+        };
+        let detection = detect_streaming(svg.as_slice(), opts, None);
+        assert!(detection.result.mime_type.is_none());
+    }
+
+    #[test]
+    fn test_detect_streaming_bounds_read_to_max_bytes() {
+        // A reader that would supply far more than `max_bytes` must only be
+        // read from up to the cap.
+        let mut body = b"<svg xmlns=\"http://www.w3.org/2000/svg\">".to_vec();
+        body.extend(std::iter::repeat(b'a').take(1_000_000));
+        let opts = DetectOptions {
+            max_bytes: 64,
+            allow_slow: true,
+        };
+        let detection = detect_streaming(body.as_slice(), opts, None);
+        // Still recognized: the SVG signature is well within the first 64 bytes.
+        assert_eq!(detection.result.mime_type.as_deref(), Some("image/svg+xml"));
+    }
+
+    #[test]
+    fn test_detect_streaming_falls_back_to_filename() {
+        let detection = detect_streaming(
+            b"".as_slice(),
+            DetectOptions::default(),
+            Some("report.pdf"),
+        );
+        assert_eq!(detection.result.mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(detection.fast_guess, Some(detection.result.clone()));
+    }
+
+    #[test]
+    fn test_utf8_prefix_handles_truncated_multibyte_boundary() {
+        // "café" ends with a 2-byte UTF-8 sequence (0xC3 0xA9); truncating
+        // mid-sequence must not make the whole prefix unreadable.
+        let full = "café".as_bytes();
+        let truncated = &full[..full.len() - 1];
+        assert_eq!(utf8_prefix(truncated), Some("caf"));
+    }
+
+    #[test]
+    fn test_utf8_prefix_rejects_invalid_bytes_with_no_valid_prefix() {
+        assert_eq!(utf8_prefix(&[0xff, 0xfe]), None);
+    }
+
+    #[test]
+    fn test_detect_streaming_svg_recognized_when_prefix_splits_multibyte_char() {
+        // A multibyte UTF-8 char (the em dash, 3 bytes) sits right at the cut
+        // point; the SVG signature at the very start must still be found.
+        let mut body = b"<svg xmlns=\"http://www.w3.org/2000/svg\">".to_vec();
+        body.extend_from_slice("em\u{2014}dash".as_bytes());
+        let cut = body.len() - 1; // splits the 3-byte em dash sequence
+        let opts = DetectOptions {
+            max_bytes: cut,
+            allow_slow: true,
+        };
+        let detection = detect_streaming(body.as_slice(), opts, None);
+        assert_eq!(detection.result.mime_type.as_deref(), Some("image/svg+xml"));
+    }
 }
@@ -17,9 +17,39 @@ pub enum FileError {
     #[error("S3 error: {0}")]
     S3(String),
 
+    /// A zip archive was malformed or couldn't be read.
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
     /// The provided file source is invalid or unsupported for the requested operation.
     #[error("Invalid source: {0}")]
     InvalidSource(String),
+
+    /// A `data:` URL was malformed or used an unsupported form.
+    #[error("Invalid data URL: {0}")]
+    DataUrl(String),
+
+    /// A recomputed digest didn't match a server-provided one (e.g. an
+    /// `ETag` or `Content-MD5` header), as returned by
+    /// [`crate::file::File::assert_integrity`].
+    #[error("Checksum mismatch ({algorithm}): expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// The server-advertised digest.
+        expected: String,
+        /// The digest actually computed over the file's contents.
+        actual: String,
+        /// The algorithm used for the comparison (e.g. `"md5"`, `"sha256"`).
+        algorithm: String,
+    },
+
+    /// The file's `expires_at` deadline has passed, as checked by
+    /// [`crate::file::File::is_expired`] before any access to its contents —
+    /// `read`/`read_text`/`read_stream`/`read_range`/`chunks`/`checksum`/`upload`/`save`.
+    #[error("File expired at {expires_at}")]
+    Expired {
+        /// The deadline that passed, as an RFC 3339 timestamp.
+        expires_at: String,
+    },
 }
 
 /// Convenience type alias for Results using FileError.
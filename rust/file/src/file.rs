@@ -3,23 +3,35 @@
 //! Provides a unified interface for creating, reading, writing, and manipulating
 //! files from different sources: URLs, local filesystem, bytes, streams, and S3.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use aws_config::BehaviorVersion;
+use aws_credential_types::provider::ProvideCredentials;
 use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::Client as S3Client;
+use base64::Engine;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use digest::{Digest, DynDigest};
+use futures::stream::FuturesUnordered;
 use futures::StreamExt;
-use sha2::{Digest, Sha256};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
 use tracing;
 
+use crate::archive::{
+    detect_archive_kind, extract_entry, list_entries, read_tar_entries, visit_tar_entries,
+    write_tar, ArchiveEntry, ArchiveEntryInfo, ArchiveInput, DEFAULT_MAX_ENTRY_SIZE,
+};
 use crate::content_disposition::parse_content_disposition;
 use crate::detection::{
     detect_from_bytes, detect_from_filename, extension_from_mime, mime_from_extension,
 };
 use crate::error::{FileError, Result};
 use crate::metadata::{Metadata, MetadataHint};
+use crate::object_store::ObjectStore;
 use crate::source::FileSource;
 
 /// A unified file type that can represent files from URLs, local filesystem,
@@ -43,10 +55,184 @@ use crate::source::FileSource;
 /// ```
 pub struct File {
     source: FileSource,
-    data: Bytes,
+    data: FileData,
     metadata: Metadata,
 }
 
+/// How a [`File`]'s body is held: already in memory, or not yet fetched.
+#[derive(Clone)]
+enum FileData {
+    /// The full body is already buffered in memory.
+    Buffered(Bytes),
+    /// The body has not been fetched yet; it is (re-)opened on demand via [`File::open_stream`].
+    Lazy(LazySource),
+}
+
+impl FileData {
+    /// Returns the buffered length, if known without fetching.
+    fn known_len(&self) -> Option<usize> {
+        match self {
+            FileData::Buffered(b) => Some(b.len()),
+            FileData::Lazy(_) => None,
+        }
+    }
+}
+
+/// Where to (re-)fetch a lazily-loaded [`File`]'s body from.
+#[derive(Clone)]
+enum LazySource {
+    /// An HTTP/HTTPS URL.
+    Url(String),
+    /// An S3 bucket and key, fetched with the given client.
+    S3 {
+        client: S3Client,
+        bucket: String,
+        key: String,
+    },
+}
+
+/// A boxed stream of file body chunks, as returned by [`File::read_stream`].
+pub type FileByteStream = std::pin::Pin<Box<dyn futures::Stream<Item = Result<Bytes>> + Send>>;
+
+/// A boxed stream of member files, as returned by [`File::tar_entries_stream`].
+pub type FileStream = std::pin::Pin<Box<dyn futures::Stream<Item = Result<File>> + Send>>;
+
+/// The result of [`File::read_range`].
+#[derive(Debug, Clone)]
+pub struct RangeRead {
+    /// The bytes returned by the backend.
+    pub data: Bytes,
+    /// Whether the backend actually honored the requested range.
+    ///
+    /// `false` means `data` is the *full* body (some HTTP servers ignore a
+    /// `Range` header and return `200 OK` instead of `206 Partial Content`);
+    /// callers that need just the window should slice `data` themselves or
+    /// treat this as a signal to fall back to a full [`File::read`].
+    pub range_honored: bool,
+}
+
+/// The result of [`File::upload`]: the remote server's response status and headers.
+#[derive(Debug, Clone)]
+pub struct UploadResponse {
+    /// The HTTP status code the server responded with.
+    pub status: u16,
+    /// The response headers, lowercased-name to value. Multi-valued headers
+    /// only retain their last occurrence.
+    pub headers: std::collections::HashMap<String, String>,
+}
+
+/// A digest algorithm usable with [`File::checksum_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// MD5 (128-bit). Matches plain S3/HTTP ETags and `Content-MD5` headers.
+    Md5,
+    /// SHA-1 (160-bit).
+    Sha1,
+    /// SHA-256 (256-bit). The default used by [`File::checksum`].
+    Sha256,
+    /// SHA-512 (512-bit).
+    Sha512,
+}
+
+impl ChecksumAlgorithm {
+    /// Create a fresh, incrementally-updatable hasher for this algorithm.
+    fn hasher(self) -> Box<dyn DynDigest> {
+        match self {
+            ChecksumAlgorithm::Md5 => Box::new(Md5::new()),
+            ChecksumAlgorithm::Sha1 => Box::new(Sha1::new()),
+            ChecksumAlgorithm::Sha256 => Box::new(Sha256::new()),
+            ChecksumAlgorithm::Sha512 => Box::new(Sha512::new()),
+        }
+    }
+
+}
+
+/// A compression algorithm usable with [`File::compress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    /// Zstandard compression.
+    Zstd,
+    /// Gzip compression.
+    Gzip,
+}
+
+impl CompressionAlgorithm {
+    /// The MIME type assigned to a [`File`] produced by [`File::compress`].
+    fn mime_type(self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Zstd => "application/zstd",
+            CompressionAlgorithm::Gzip => "application/gzip",
+        }
+    }
+
+    /// The file extension appended to a [`File`] produced by [`File::compress`].
+    fn extension(self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Zstd => "zst",
+            CompressionAlgorithm::Gzip => "gz",
+        }
+    }
+}
+
+/// Options for [`File::get_signed_upload_url`]/[`File::get_signed_upload_url_with_client`],
+/// mapped onto the `PutObject` request before presigning.
+#[derive(Debug, Clone, Default)]
+pub struct SignedUploadUrlOptions {
+    /// The `Content-Type` the uploaded object must be sent with.
+    pub content_type: Option<String>,
+    /// The exact `Content-Length` the uploaded object must be sent with.
+    pub content_length: Option<u64>,
+    /// A canned ACL (e.g. `"private"`, `"public-read"`) to apply to the uploaded object.
+    pub acl: Option<String>,
+}
+
+/// The target URL and form fields for a browser `multipart/form-data` `POST`
+/// upload directly to S3, as returned by [`File::presigned_post`].
+#[derive(Debug, Clone)]
+pub struct PresignedPost {
+    /// The form's `action` URL (the bucket's regional endpoint).
+    pub url: String,
+    /// The form fields to submit alongside the file, including the policy
+    /// document and its signature.
+    pub fields: std::collections::HashMap<String, String>,
+}
+
+/// Configuration for targeting an S3-compatible object store (MinIO, Wasabi,
+/// Yandex Object Storage, Garage, etc.) instead of AWS S3, via
+/// [`File::from_s3_with_endpoint`] and the `_with_endpoint` signing methods.
+#[derive(Debug, Clone, Default)]
+pub struct S3Config {
+    /// A custom endpoint URL (e.g. `"http://localhost:9000"` for MinIO).
+    pub endpoint_url: Option<String>,
+    /// The region to sign requests for.
+    pub region: Option<String>,
+    /// Whether to address buckets as `{endpoint}/{bucket}/{key}` instead of
+    /// `{bucket}.{endpoint}/{key}`. Most self-hosted S3-compatible stores need this.
+    pub force_path_style: bool,
+}
+
+impl S3Config {
+    /// Build an S3 client from this configuration, loading credentials from
+    /// the environment/default provider chain as usual.
+    pub async fn build_client(&self) -> S3Client {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = &self.region {
+            loader = loader.region(aws_sdk_s3::config::Region::new(region.clone()));
+        }
+        let base = loader.load().await;
+
+        let mut builder = aws_sdk_s3::config::Builder::from(&base);
+        if let Some(endpoint) = &self.endpoint_url {
+            builder = builder.endpoint_url(endpoint.clone());
+        }
+        if self.force_path_style {
+            builder = builder.force_path_style(true);
+        }
+
+        S3Client::from_conf(builder.build())
+    }
+}
+
 impl File {
     // -----------------------------------------------------------------------
     // Constructors
@@ -95,7 +281,7 @@ impl File {
 
         Ok(Self {
             source: FileSource::Bytes,
-            data,
+            data: FileData::Buffered(data),
             metadata,
         })
     }
@@ -168,13 +354,57 @@ impl File {
 
         Ok(Self {
             source: FileSource::File,
-            data,
+            data: FileData::Buffered(data),
             metadata,
         })
     }
 
-    /// Create a `File` from an HTTP/HTTPS URL.
+    /// Create a `File` from a `data:` URL (RFC 2397).
+    ///
+    /// The declared media type seeds `mime_type`/`extension`, but the
+    /// decoded payload is then run through [`detect_from_bytes`] so a
+    /// mislabeled data URL (e.g. `data:text/plain,...` actually holding a
+    /// PNG) still resolves to the right type. See [`crate::data_url::from_data_url`].
+    pub async fn from_data_url(uri: &str, hint: Option<MetadataHint>) -> Result<Self> {
+        let (data, mut metadata) = crate::data_url::from_data_url(uri)?;
+
+        if let Some(h) = &hint {
+            metadata.merge_hints(h);
+        }
+
+        tracing::info!(?metadata, "File created from data URL");
+
+        Ok(Self {
+            source: FileSource::DataUrl,
+            data: FileData::Buffered(Bytes::from(data)),
+            metadata,
+        })
+    }
+
+    /// Create a `File` from an HTTP/HTTPS URL, transparently decompressing a
+    /// `Content-Encoding: gzip`/`br`/`deflate`/`zstd` response body.
+    ///
+    /// Equivalent to [`File::from_url_with_options`] with
+    /// `decode_content_encoding: true`.
     pub async fn from_url(url: &str, hint: Option<MetadataHint>) -> Result<Self> {
+        Self::from_url_with_options(url, hint, true).await
+    }
+
+    /// Create a `File` from an HTTP/HTTPS URL, with explicit control over
+    /// whether a `Content-Encoding` response body is decompressed.
+    ///
+    /// When `decode_content_encoding` is `true` (the default via
+    /// [`File::from_url`]), a `Content-Encoding` of `gzip`, `br`, `deflate`,
+    /// or `zstd` is decoded before the bytes are stored and magic-byte
+    /// detection runs, so `read_text`/detection see the real payload instead
+    /// of misfiring on still-compressed bytes. Passing `false` stores the
+    /// response body exactly as received, for callers that want the raw
+    /// compressed bytes.
+    pub async fn from_url_with_options(
+        url: &str,
+        hint: Option<MetadataHint>,
+        decode_content_encoding: bool,
+    ) -> Result<Self> {
         let response = reqwest::get(url).await?;
 
         let mut metadata = Metadata::new();
@@ -192,8 +422,8 @@ impl File {
         if let Some(cd_header) = headers.get("content-disposition") {
             if let Ok(cd_str) = cd_header.to_str() {
                 if let Some(cd) = parse_content_disposition(cd_str) {
-                    if let Some(fname) = cd.filename {
-                        metadata.name = Some(fname);
+                    if let Some(fname) = cd.get_filename() {
+                        metadata.name = Some(fname.to_string());
                     }
                 }
             }
@@ -213,7 +443,9 @@ impl File {
             }
         }
 
-        // Content-Length
+        // Content-Length (this is the on-the-wire size; if the body is
+        // Content-Encoding-compressed and gets decoded below, the decoded
+        // length overrides this).
         if let Some(cl) = headers.get("content-length") {
             if let Ok(cl_str) = cl.to_str() {
                 if let Ok(size) = cl_str.parse::<u64>() {
@@ -222,6 +454,11 @@ impl File {
             }
         }
 
+        let content_encoding = headers
+            .get("content-encoding")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.trim().to_ascii_lowercase());
+
         // ETag / Content-MD5
         if let Some(etag) = headers.get("etag") {
             if let Ok(etag_str) = etag.to_str() {
@@ -244,14 +481,49 @@ impl File {
             }
         }
 
-        // Read the body
-        let data = Bytes::from(response.bytes().await?);
-
-        // Override size from actual data if not set from headers
-        if metadata.size.is_none() {
-            metadata.size = Some(data.len() as u64);
+        // Expires: a date in the past (or the literal "0", which some
+        // servers send to mean "already expired") marks the file expired
+        // immediately.
+        if let Some(expires) = headers.get("expires") {
+            if let Ok(expires_str) = expires.to_str() {
+                if let Ok(dt) = DateTime::parse_from_rfc2822(expires_str) {
+                    metadata.expires_at = Some(dt.with_timezone(&Utc));
+                } else if expires_str.trim() == "0" {
+                    metadata.expires_at = Some(Utc::now());
+                }
+            }
         }
 
+        // Read the body
+        let raw_data = response.bytes().await?.to_vec();
+
+        let data = match (&content_encoding, decode_content_encoding) {
+            (Some(encoding), true) if encoding != "identity" => {
+                let encoding = encoding.clone();
+                let decoded = tokio::task::spawn_blocking(move || {
+                    decode_content_encoding(&encoding, &raw_data)
+                })
+                .await
+                .map_err(|e| {
+                    FileError::InvalidSource(format!("Content-Encoding decode task panicked: {}", e))
+                })??;
+                // The decoded length replaces the compressed Content-Length.
+                metadata.size = Some(decoded.len() as u64);
+                // The ETag/Content-MD5 above digests the compressed wire
+                // representation, not these decoded bytes; keeping it would
+                // make verify_integrity compare against the wrong digest.
+                metadata.hash = None;
+                Bytes::from(decoded)
+            }
+            _ => {
+                let data = Bytes::from(raw_data);
+                if metadata.size.is_none() {
+                    metadata.size = Some(data.len() as u64);
+                }
+                data
+            }
+        };
+
         // Detect from bytes (may override mime from response if magic bytes are definitive)
         let detection = detect_from_bytes(&data, metadata.name.as_deref());
         if let Some(det_mime) = &detection.mime_type {
@@ -295,7 +567,114 @@ impl File {
 
         Ok(Self {
             source: FileSource::Url,
-            data,
+            data: FileData::Buffered(data),
+            metadata,
+        })
+    }
+
+    /// Create a `File` from an HTTP/HTTPS URL, then verify its contents
+    /// against the server-provided `ETag`/`Content-MD5` digest before
+    /// returning it.
+    ///
+    /// Equivalent to calling [`File::from_url`] followed by
+    /// [`File::assert_integrity`]; returns a
+    /// [`FileError::ChecksumMismatch`](crate::error::FileError::ChecksumMismatch)
+    /// if the downloaded bytes don't match. Useful when fetching from a
+    /// source where a corrupted or tampered download must be caught
+    /// immediately rather than surfacing later as bad data.
+    pub async fn from_url_verified(url: &str, hint: Option<MetadataHint>) -> Result<Self> {
+        let file = Self::from_url(url, hint).await?;
+        file.assert_integrity().await?;
+        Ok(file)
+    }
+
+    /// Create a `File` from an HTTP/HTTPS URL without downloading the body.
+    ///
+    /// Metadata is populated from a `HEAD` request; the body is fetched on demand
+    /// by [`File::read`], [`File::read_stream`], or similar. Because the body isn't
+    /// available up front, magic-byte MIME detection doesn't run here.
+    pub async fn from_url_lazy(url: &str, hint: Option<MetadataHint>) -> Result<Self> {
+        let client = reqwest::Client::new();
+        let response = client.head(url).send().await?;
+
+        let mut metadata = Metadata::new();
+        metadata.url = Some(url.to_string());
+
+        if let Some(h) = &hint {
+            metadata.merge_hints(h);
+        }
+
+        let headers = response.headers();
+
+        if let Some(cd_header) = headers.get("content-disposition") {
+            if let Ok(cd_str) = cd_header.to_str() {
+                if let Some(cd) = parse_content_disposition(cd_str) {
+                    if let Some(fname) = cd.get_filename() {
+                        metadata.name = Some(fname.to_string());
+                    }
+                }
+            }
+        }
+
+        if metadata.name.is_none() {
+            metadata.name = get_filename_from_url(url);
+        }
+
+        if let Some(ct) = headers.get("content-type") {
+            if let Ok(ct_str) = ct.to_str() {
+                let mime_part = ct_str.split(';').next().unwrap_or(ct_str).trim();
+                metadata.mime_type = Some(mime_part.to_string());
+            }
+        }
+
+        if let Some(cl) = headers.get("content-length") {
+            if let Ok(cl_str) = cl.to_str() {
+                if let Ok(size) = cl_str.parse::<u64>() {
+                    metadata.size = Some(size);
+                }
+            }
+        }
+
+        if let Some(etag) = headers.get("etag") {
+            if let Ok(etag_str) = etag.to_str() {
+                metadata.hash = Some(etag_str.trim_matches('"').to_string());
+            }
+        } else if let Some(md5) = headers.get("content-md5") {
+            if let Ok(md5_str) = md5.to_str() {
+                metadata.hash = Some(md5_str.to_string());
+            }
+        }
+
+        if let Some(lm) = headers.get("last-modified") {
+            if let Ok(lm_str) = lm.to_str() {
+                if let Ok(dt) = DateTime::parse_from_rfc2822(lm_str) {
+                    metadata.last_modified = Some(dt.with_timezone(&Utc));
+                } else if let Ok(dt) = DateTime::parse_from_rfc3339(lm_str) {
+                    metadata.last_modified = Some(dt.with_timezone(&Utc));
+                }
+            }
+        }
+
+        if metadata.mime_type.is_none() {
+            if let Some(name) = &metadata.name {
+                let det = detect_from_filename(name);
+                metadata.mime_type = det.mime_type;
+                if metadata.extension.is_none() {
+                    metadata.extension = det.extension;
+                }
+            }
+        }
+        if metadata.extension.is_none() {
+            if let Some(mime) = &metadata.mime_type {
+                metadata.extension = extension_from_mime(mime);
+            }
+        }
+
+        tracing::info!(?metadata, "File created from URL (lazy)");
+
+        Ok(Self {
+            source: FileSource::Url,
+            data: FileData::Lazy(LazySource::Url(url.to_string())),
             metadata,
         })
     }
@@ -351,7 +730,7 @@ impl File {
 
         Ok(Self {
             source: FileSource::Stream,
-            data,
+            data: FileData::Buffered(data),
             metadata,
         })
     }
@@ -407,8 +786,8 @@ impl File {
         // Content-Disposition
         if let Some(cd_str) = resp.content_disposition() {
             if let Some(cd) = parse_content_disposition(cd_str) {
-                if let Some(fname) = cd.filename {
-                    metadata.name = Some(fname);
+                if let Some(fname) = cd.get_filename() {
+                    metadata.name = Some(fname.to_string());
                 }
             }
         }
@@ -456,7 +835,7 @@ impl File {
 
         Ok(Self {
             source: FileSource::S3,
-            data,
+            data: FileData::Buffered(data),
             metadata,
         })
     }
@@ -509,8 +888,8 @@ impl File {
         }
         if let Some(cd_str) = resp.content_disposition() {
             if let Some(cd) = parse_content_disposition(cd_str) {
-                if let Some(fname) = cd.filename {
-                    metadata.name = Some(fname);
+                if let Some(fname) = cd.get_filename() {
+                    metadata.name = Some(fname.to_string());
                 }
             }
         }
@@ -551,59 +930,241 @@ impl File {
 
         Ok(Self {
             source: FileSource::S3,
-            data,
+            data: FileData::Buffered(data),
             metadata,
         })
     }
 
-    // -----------------------------------------------------------------------
-    // Accessors
-    // -----------------------------------------------------------------------
-
-    /// Returns the file source type.
-    pub fn source(&self) -> FileSource {
-        self.source
-    }
-
-    /// Returns a reference to the full metadata.
-    pub fn metadata(&self) -> &Metadata {
-        &self.metadata
-    }
-
-    /// Returns the file name, if known.
-    pub fn name(&self) -> Option<&str> {
-        self.metadata.name.as_deref()
-    }
-
-    /// Returns the MIME type, if known.
-    pub fn mime_type(&self) -> Option<&str> {
-        self.metadata.mime_type.as_deref()
+    /// Create a `File` from an S3-compatible bucket and key, targeting a
+    /// custom endpoint (MinIO, Wasabi, Yandex Object Storage, Garage, etc.)
+    /// per `s3_config` instead of AWS S3.
+    pub async fn from_s3_with_endpoint(
+        bucket: &str,
+        key: &str,
+        s3_config: &S3Config,
+        hint: Option<MetadataHint>,
+    ) -> Result<Self> {
+        let client = s3_config.build_client().await;
+        Self::from_s3_with_client(&client, bucket, key, hint).await
     }
 
-    /// Returns the file size in bytes, if known.
-    pub fn size(&self) -> Option<u64> {
-        self.metadata.size
-    }
+    /// Create a `File` from an S3 bucket and key without downloading the body.
+    ///
+    /// Metadata is populated from a `HeadObject` call; the body is fetched on demand
+    /// by [`File::read`], [`File::read_stream`], or similar (re-issuing `GetObject`
+    /// each time). This keeps memory bounded when only a few files out of a large
+    /// batch end up being read in full.
+    pub async fn from_s3_lazy(
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        hint: Option<MetadataHint>,
+    ) -> Result<Self> {
+        let resp = client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
 
-    /// Returns the file extension (without dot), if known.
-    pub fn extension(&self) -> Option<&str> {
-        self.metadata.extension.as_deref()
-    }
+        let mut metadata = Metadata::new();
+        metadata.url = Some(format!("s3://{}/{}", bucket, key));
 
-    /// Returns the URL the file was loaded from, if applicable.
-    pub fn url(&self) -> Option<&str> {
-        self.metadata.url.as_deref()
-    }
+        metadata.name = Path::new(key)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string());
 
-    /// Returns the filesystem path, if applicable.
-    pub fn path(&self) -> Option<&str> {
-        self.metadata.path.as_deref()
-    }
+        if let Some(h) = &hint {
+            metadata.merge_hints(h);
+        }
 
-    /// Returns the hash/etag, if known.
-    pub fn hash(&self) -> Option<&str> {
-        self.metadata.hash.as_deref()
-    }
+        if let Some(ct) = resp.content_type() {
+            metadata.mime_type = Some(ct.to_string());
+        }
+        if let Some(cl) = resp.content_length() {
+            if cl > 0 {
+                metadata.size = Some(cl as u64);
+            }
+        }
+        if let Some(etag) = resp.e_tag() {
+            metadata.hash = Some(etag.trim_matches('"').to_string());
+        }
+        if let Some(lm) = resp.last_modified() {
+            let epoch_secs = lm.secs();
+            let subsec_nanos = lm.subsec_nanos();
+            if let Some(dt) = DateTime::from_timestamp(epoch_secs, subsec_nanos) {
+                metadata.last_modified = Some(dt);
+            }
+        }
+        if let Some(cd_str) = resp.content_disposition() {
+            if let Some(cd) = parse_content_disposition(cd_str) {
+                if let Some(fname) = cd.get_filename() {
+                    metadata.name = Some(fname.to_string());
+                }
+            }
+        }
+
+        if metadata.mime_type.is_none() {
+            if let Some(name) = &metadata.name {
+                let det = detect_from_filename(name);
+                metadata.mime_type = det.mime_type;
+                if metadata.extension.is_none() {
+                    metadata.extension = det.extension;
+                }
+            }
+        }
+        if metadata.extension.is_none() {
+            if let Some(mime) = &metadata.mime_type {
+                metadata.extension = extension_from_mime(mime);
+            }
+        }
+
+        tracing::info!(?metadata, "File created from S3 (lazy)");
+
+        Ok(Self {
+            source: FileSource::S3,
+            data: FileData::Lazy(LazySource::S3 {
+                client: client.clone(),
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            }),
+            metadata,
+        })
+    }
+
+    /// Create a `File` from a bucket and key via a backend-agnostic [`ObjectStore`]
+    /// (e.g. [`crate::object_store::S3Store`], [`crate::object_store::AzureBlobStore`],
+    /// or [`crate::object_store::GcsStore`]).
+    pub async fn from_object_store(
+        store: &dyn ObjectStore,
+        bucket: &str,
+        key: &str,
+        hint: Option<MetadataHint>,
+    ) -> Result<Self> {
+        let obj = store.get(bucket, key).await?;
+
+        let mut metadata = Metadata::new();
+        metadata.url = Some(format!("{}/{}", bucket, key));
+
+        metadata.name = Path::new(key)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string());
+
+        if let Some(h) = &hint {
+            metadata.merge_hints(h);
+        }
+
+        if let Some(ct) = obj.content_type {
+            metadata.mime_type = Some(ct);
+        }
+        if let Some(cl) = obj.content_length {
+            metadata.size = Some(cl);
+        }
+        if metadata.hash.is_none() {
+            metadata.hash = obj.etag;
+        }
+        if let Some(lm) = obj.last_modified {
+            metadata.last_modified = Some(lm);
+        }
+        if let Some(version) = obj.version {
+            metadata.version = Some(version);
+        }
+        if let Some(cd_str) = &obj.content_disposition {
+            if let Some(cd) = parse_content_disposition(cd_str) {
+                if let Some(fname) = cd.get_filename() {
+                    metadata.name = Some(fname.to_string());
+                }
+            }
+        }
+
+        let data = obj.data;
+
+        if metadata.size.is_none() {
+            metadata.size = Some(data.len() as u64);
+        }
+
+        let detection = detect_from_bytes(&data, metadata.name.as_deref());
+        if metadata.extension.is_none() {
+            metadata.extension = detection.extension;
+        }
+        if metadata.mime_type.is_none() {
+            metadata.mime_type = detection.mime_type;
+        }
+        if metadata.mime_type.is_none() {
+            if let Some(name) = &metadata.name {
+                let det = detect_from_filename(name);
+                metadata.mime_type = det.mime_type;
+                if metadata.extension.is_none() {
+                    metadata.extension = det.extension;
+                }
+            }
+        }
+        if metadata.extension.is_none() {
+            if let Some(mime) = &metadata.mime_type {
+                metadata.extension = extension_from_mime(mime);
+            }
+        }
+
+        tracing::info!(?metadata, "File created from object store");
+
+        Ok(Self {
+            source: FileSource::ObjectStore,
+            data: FileData::Buffered(data),
+            metadata,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Accessors
+    // -----------------------------------------------------------------------
+
+    /// Returns the file source type.
+    pub fn source(&self) -> FileSource {
+        self.source
+    }
+
+    /// Returns a reference to the full metadata.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the file name, if known.
+    pub fn name(&self) -> Option<&str> {
+        self.metadata.name.as_deref()
+    }
+
+    /// Returns the MIME type, if known.
+    pub fn mime_type(&self) -> Option<&str> {
+        self.metadata.mime_type.as_deref()
+    }
+
+    /// Returns the file size in bytes, if known.
+    pub fn size(&self) -> Option<u64> {
+        self.metadata.size
+    }
+
+    /// Returns the file extension (without dot), if known.
+    pub fn extension(&self) -> Option<&str> {
+        self.metadata.extension.as_deref()
+    }
+
+    /// Returns the URL the file was loaded from, if applicable.
+    pub fn url(&self) -> Option<&str> {
+        self.metadata.url.as_deref()
+    }
+
+    /// Returns the filesystem path, if applicable.
+    pub fn path(&self) -> Option<&str> {
+        self.metadata.path.as_deref()
+    }
+
+    /// Returns the hash/etag, if known.
+    pub fn hash(&self) -> Option<&str> {
+        self.metadata.hash.as_deref()
+    }
 
     /// Returns when the file was last modified, if known.
     pub fn last_modified(&self) -> Option<DateTime<Utc>> {
@@ -615,18 +1176,387 @@ impl File {
         self.metadata.created_at
     }
 
+    /// Returns when the file's contents should be considered gone, if set.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.metadata.expires_at
+    }
+
+    /// Whether this file's [`File::expires_at`] deadline has passed.
+    ///
+    /// Returns `false` when no expiry is set.
+    pub fn is_expired(&self) -> bool {
+        self.metadata.expires_at.is_some_and(|deadline| deadline <= Utc::now())
+    }
+
+    /// Returns [`FileError::Expired`] if [`File::is_expired`] is true.
+    fn check_not_expired(&self) -> Result<()> {
+        if let Some(deadline) = self.metadata.expires_at {
+            if deadline <= Utc::now() {
+                return Err(FileError::Expired {
+                    expires_at: deadline.to_rfc3339(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Read operations
     // -----------------------------------------------------------------------
 
     /// Read the file contents as raw bytes.
+    ///
+    /// For a lazily-loaded file this fetches and buffers the entire body.
+    /// Use [`File::read_stream`] to avoid buffering large lazy files.
+    ///
+    /// Returns [`FileError::Expired`] if [`File::is_expired`] is true.
     pub async fn read(&self) -> Result<Bytes> {
-        Ok(self.data.clone())
+        self.check_not_expired()?;
+        match &self.data {
+            FileData::Buffered(bytes) => Ok(bytes.clone()),
+            FileData::Lazy(_) => {
+                let mut stream = self.open_stream().await?;
+                let mut buf = Vec::new();
+                while let Some(chunk) = stream.next().await {
+                    buf.extend_from_slice(&chunk?);
+                }
+                Ok(Bytes::from(buf))
+            }
+        }
     }
 
-    /// Read the file contents as a UTF-8 string.
+    /// Read the file contents as a string, decoding with UTF-8 (lossy on
+    /// invalid sequences) unless a BOM or declared charset says otherwise.
+    ///
+    /// Equivalent to `read_text_with_encoding(None, false)`. See
+    /// [`File::read_text_with_encoding`] for the full detection order.
+    ///
+    /// Returns [`FileError::Expired`] if [`File::is_expired`] is true.
     pub async fn read_text(&self) -> Result<String> {
-        Ok(String::from_utf8_lossy(&self.data).to_string())
+        self.read_text_with_encoding(None, false).await
+    }
+
+    /// Read the file contents as a string, decoding via `encoding_rs`.
+    ///
+    /// The encoding is resolved in priority order:
+    /// 1. A BOM sniffed from the leading bytes (`EF BB BF` → UTF-8, `FF FE` →
+    ///    UTF-16LE, `FE FF` → UTF-16BE), stripped from the decoded output.
+    /// 2. `encoding`, if given by the caller.
+    /// 3. A `charset` parameter on `metadata.mime_type` (e.g.
+    ///    `text/plain; charset=windows-1255`), resolved via
+    ///    `Encoding::for_label`.
+    /// 4. UTF-8.
+    ///
+    /// When `strict` is `true`, malformed sequences produce a `FileError`
+    /// instead of being replaced with the Unicode replacement character.
+    pub async fn read_text_with_encoding(
+        &self,
+        encoding: Option<&'static encoding_rs::Encoding>,
+        strict: bool,
+    ) -> Result<String> {
+        let bytes = self.read().await?;
+
+        let (resolved, body): (&'static encoding_rs::Encoding, &[u8]) =
+            if let Some((enc, bom_len)) = encoding_rs::Encoding::for_bom(&bytes) {
+                (enc, &bytes[bom_len..])
+            } else if let Some(enc) = encoding.or_else(|| {
+                self.mime_charset()
+                    .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+            }) {
+                (enc, bytes.as_ref())
+            } else {
+                (encoding_rs::UTF_8, bytes.as_ref())
+            };
+
+        let (decoded, had_errors) = resolved.decode_without_bom_handling(body);
+        if strict && had_errors {
+            return Err(FileError::InvalidSource(format!(
+                "Invalid {} byte sequence while decoding file contents",
+                resolved.name()
+            )));
+        }
+
+        Ok(decoded.into_owned())
+    }
+
+    /// Extract the `charset` parameter from `metadata.mime_type`, if present
+    /// (e.g. `"text/plain; charset=windows-1255"` -> `"windows-1255"`).
+    fn mime_charset(&self) -> Option<String> {
+        let mime = self.metadata.mime_type.as_deref()?;
+        mime.split(';').skip(1).find_map(|param| {
+            param
+                .trim()
+                .strip_prefix("charset=")
+                .map(|v| v.trim_matches('"').to_string())
+        })
+    }
+
+    /// Encode this file as a `data:[<mime>];base64,<data>` URL (RFC 2397).
+    ///
+    /// `metadata.mime_type` is used when present; otherwise the MIME type is
+    /// inferred from the bytes via [`detect_from_bytes`], falling back to
+    /// `application/octet-stream`. See [`crate::data_url::to_data_url`].
+    pub async fn to_data_url(&self) -> Result<String> {
+        let bytes = self.read().await?;
+        Ok(crate::data_url::to_data_url(
+            &bytes,
+            self.metadata.mime_type.as_deref(),
+        ))
+    }
+
+    /// Stream the file contents as a sequence of byte chunks without buffering
+    /// the whole body in memory.
+    ///
+    /// For a buffered file this yields the existing bytes as a single chunk.
+    /// For a lazily-loaded file (see [`File::from_url_lazy`], [`File::from_s3_lazy`])
+    /// this (re-)opens the underlying URL or S3 object and streams its body directly.
+    pub async fn read_stream(&self) -> Result<FileByteStream> {
+        self.check_not_expired()?;
+        self.open_stream().await
+    }
+
+    /// Open a fresh byte stream over this file's body.
+    async fn open_stream(&self) -> Result<FileByteStream> {
+        match &self.data {
+            FileData::Buffered(bytes) => {
+                let bytes = bytes.clone();
+                Ok(Box::pin(futures::stream::once(
+                    async move { Ok(bytes) },
+                )))
+            }
+            FileData::Lazy(LazySource::Url(url)) => {
+                let response = reqwest::get(url).await?;
+                Ok(Box::pin(
+                    response.bytes_stream().map(|r| r.map_err(FileError::from)),
+                ))
+            }
+            FileData::Lazy(LazySource::S3 { client, bucket, key }) => {
+                let resp = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(key)
+                    .send()
+                    .await
+                    .map_err(|e| FileError::S3(e.to_string()))?;
+                Ok(Box::pin(
+                    resp.body.map(|r| r.map_err(|e| FileError::S3(e.to_string()))),
+                ))
+            }
+        }
+    }
+
+    /// Read a byte-range slice of the file's contents, fetching only that
+    /// window where the backend supports it.
+    ///
+    /// - [`FileSource::Url`]: issues an HTTP `Range` request.
+    /// - [`FileSource::S3`]: issues an S3 `GetObject` with a `Range` header.
+    /// - [`FileSource::File`]: seeks the underlying path and reads `len` bytes.
+    /// - Everything else (already in memory): subslices the buffered bytes.
+    ///
+    /// `len` is clamped to the remaining length where the full length is
+    /// known; requesting past the end of the file returns whatever bytes
+    /// remain. [`RangeRead::range_honored`] reports whether the backend
+    /// actually returned just the requested window.
+    pub async fn read_range(&self, start: u64, len: u64) -> Result<RangeRead> {
+        self.check_not_expired()?;
+        match self.source {
+            FileSource::Url => self.read_range_url(start, len).await,
+            FileSource::S3 => self.read_range_s3(start, len).await,
+            FileSource::File => self.read_range_file(start, len).await,
+            FileSource::Bytes | FileSource::Stream | FileSource::ObjectStore | FileSource::DataUrl => {
+                let data = self.read().await?;
+                Ok(RangeRead {
+                    data: slice_range(&data, start, len),
+                    range_honored: true,
+                })
+            }
+        }
+    }
+
+    async fn read_range_url(&self, start: u64, len: u64) -> Result<RangeRead> {
+        if len == 0 {
+            return Ok(RangeRead {
+                data: Bytes::new(),
+                range_honored: true,
+            });
+        }
+
+        let url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("URL file is missing URL metadata".to_string())
+        })?;
+
+        let end = start + len.saturating_sub(1);
+        let response = reqwest::Client::new()
+            .get(url)
+            .header("Range", format!("bytes={}-{}", start, end))
+            .send()
+            .await?;
+
+        let range_honored = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        let data = Bytes::from(response.bytes().await?);
+        Ok(RangeRead {
+            data,
+            range_honored,
+        })
+    }
+
+    async fn read_range_s3(&self, start: u64, len: u64) -> Result<RangeRead> {
+        if len == 0 {
+            return Ok(RangeRead {
+                data: Bytes::new(),
+                range_honored: true,
+            });
+        }
+
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+        let (bucket, key) = parse_s3_url(s3_url)?;
+
+        let client = match &self.data {
+            FileData::Lazy(LazySource::S3 { client, .. }) => client.clone(),
+            _ => {
+                let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+                S3Client::new(&config)
+            }
+        };
+
+        let end = start + len.saturating_sub(1);
+        let resp = client
+            .get_object()
+            .bucket(&bucket)
+            .key(&key)
+            .range(format!("bytes={}-{}", start, end))
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        let range_honored = resp.content_range().is_some();
+        let body_bytes = resp
+            .body
+            .collect()
+            .await
+            .map_err(|e| FileError::S3(format!("Failed to read S3 body: {}", e)))?
+            .into_bytes();
+
+        Ok(RangeRead {
+            data: Bytes::from(body_bytes.to_vec()),
+            range_honored,
+        })
+    }
+
+    async fn read_range_file(&self, start: u64, len: u64) -> Result<RangeRead> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let path = self.metadata.path.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("File is missing a path to seek into".to_string())
+        })?;
+
+        let mut file = tokio::fs::File::open(path).await?;
+        let file_len = file.metadata().await?.len();
+        file.seek(std::io::SeekFrom::Start(start)).await?;
+
+        // Clamp to the file's actual size so a caller-supplied `len` well
+        // past the end doesn't force an upfront allocation for data that
+        // isn't there to read.
+        let len = len.min(file_len.saturating_sub(start));
+
+        let mut buf = vec![0u8; len as usize];
+        let mut total = 0;
+        loop {
+            let n = file.read(&mut buf[total..]).await?;
+            if n == 0 {
+                break;
+            }
+            total += n;
+            if total == buf.len() {
+                break;
+            }
+        }
+        buf.truncate(total);
+
+        Ok(RangeRead {
+            data: Bytes::from(buf),
+            range_honored: true,
+        })
+    }
+
+    /// Like [`File::read_range`], but takes any `RangeBounds<u64>` (e.g.
+    /// `6..`, `..10`, `6..=10`) instead of a separate `start`/`len` pair.
+    ///
+    /// Unbounded/open ends are resolved against [`File::size`]; an
+    /// open-ended range on a file whose size isn't known, or a `start` past
+    /// the known size, is rejected.
+    pub async fn read_range_bounds(&self, range: impl std::ops::RangeBounds<u64>) -> Result<RangeRead> {
+        use std::ops::Bound;
+
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+
+        if let Some(size) = self.size() {
+            if start > size {
+                return Err(FileError::InvalidSource(format!(
+                    "range start {} exceeds file size {}",
+                    start, size
+                )));
+            }
+        }
+
+        let end_exclusive = match range.end_bound() {
+            Bound::Included(&e) => Some(e + 1),
+            Bound::Excluded(&e) => Some(e),
+            Bound::Unbounded => self.size(),
+        }
+        .ok_or_else(|| {
+            FileError::InvalidSource(
+                "an open-ended range requires a known file size".to_string(),
+            )
+        })?;
+
+        self.read_range(start, end_exclusive.saturating_sub(start))
+            .await
+    }
+
+    /// Stream the file's contents in fixed-size chunks, without buffering the
+    /// whole body in memory at once.
+    ///
+    /// Requires a known [`File::size`] to know where to stop. Each chunk is
+    /// fetched via [`File::read_range`], so it inherits that method's
+    /// per-source behavior (an HTTP `Range` request, an S3 ranged `GetObject`,
+    /// a seeked read, or an in-memory subslice).
+    pub async fn chunks(&self, chunk_size: u64) -> Result<FileByteStream> {
+        if chunk_size == 0 {
+            return Err(FileError::InvalidSource(
+                "chunk_size must be greater than zero".to_string(),
+            ));
+        }
+
+        let total = self.size().ok_or_else(|| {
+            FileError::InvalidSource("cannot chunk a file with unknown size".to_string())
+        })?;
+
+        let file = File {
+            source: self.source,
+            data: self.data.clone(),
+            metadata: self.metadata.clone(),
+        };
+
+        Ok(Box::pin(futures::stream::unfold(
+            (file, 0u64),
+            move |(file, offset)| async move {
+                if offset >= total {
+                    return None;
+                }
+                let len = (total - offset).min(chunk_size);
+                match file.read_range(offset, len).await {
+                    Ok(range) => Some((Ok(range.data), (file, offset + len))),
+                    Err(e) => Some((Err(e), (file, total))),
+                }
+            },
+        )))
     }
 
     // -----------------------------------------------------------------------
@@ -635,10 +1565,17 @@ impl File {
 
     /// Save the file to a local filesystem path.
     ///
-    /// Returns a tuple of the original file and a new file representing the saved copy.
+    /// Returns a tuple of the original file and a new file representing the
+    /// saved copy; the saved copy's `expires_at` carries over from the
+    /// original. Returns [`FileError::Expired`] if [`File::is_expired`] is true.
     pub async fn save(&self, destination: &str) -> Result<(File, File)> {
-        tokio::fs::write(destination, &self.data).await?;
-        let new_file = File::from_file(destination, None).await?;
+        self.check_not_expired()?;
+        self.write_to_path(destination).await?;
+        let hint = MetadataHint {
+            expires_at: self.metadata.expires_at,
+            ..Default::default()
+        };
+        let new_file = File::from_file(destination, Some(hint)).await?;
         // Clone self for the "original" return
         let original = File {
             source: self.source,
@@ -650,9 +1587,11 @@ impl File {
 
     /// Move the file to a new location on the filesystem.
     ///
-    /// If the file was originally from the filesystem, the source file is deleted.
+    /// If the file was originally from the filesystem, the source file is
+    /// deleted. Returns [`FileError::Expired`] if [`File::is_expired`] is true.
     pub async fn move_to(&self, destination: &str) -> Result<File> {
-        tokio::fs::write(destination, &self.data).await?;
+        self.check_not_expired()?;
+        self.write_to_path(destination).await?;
 
         // Delete original if it was a filesystem file
         if self.source == FileSource::File {
@@ -664,80 +1603,896 @@ impl File {
         File::from_file(destination, None).await
     }
 
-    /// Delete the file from the filesystem.
-    ///
-    /// Only works for files with source `FileSource::File`.
-    pub async fn delete(&self) -> Result<()> {
-        if self.source == FileSource::File {
-            if let Some(path) = &self.metadata.path {
-                tokio::fs::remove_file(path).await?;
+    /// Save the file to a local filesystem path, avoiding collisions instead
+    /// of overwriting an existing file there.
+    ///
+    /// If `destination` already exists, a random `-0x{16 hex digits}` suffix
+    /// is appended to its stem (replacing any such suffix already present,
+    /// so repeated saves don't stack suffixes) and the write is retried
+    /// atomically via `create_new` up to [`UNIQUE_SAVE_ATTEMPTS`] times. The
+    /// returned `File`'s `path`/`name` reflect whichever path was actually
+    /// written. Returns [`FileError::Expired`] if [`File::is_expired`] is true.
+    pub async fn save_unique(&self, destination: &str) -> Result<(File, File)> {
+        self.check_not_expired()?;
+        let final_path = self.write_to_unique_path(destination).await?;
+        let new_file = File::from_file(&final_path, None).await?;
+        let original = File {
+            source: self.source,
+            data: self.data.clone(),
+            metadata: self.metadata.clone(),
+        };
+        Ok((original, new_file))
+    }
+
+    /// Move the file to a new location on the filesystem, avoiding collisions
+    /// instead of overwriting an existing file there.
+    ///
+    /// See [`File::save_unique`] for how the final path is chosen. If the
+    /// file was originally from the filesystem, the source file is deleted.
+    /// Returns [`FileError::Expired`] if [`File::is_expired`] is true.
+    pub async fn move_to_unique(&self, destination: &str) -> Result<File> {
+        self.check_not_expired()?;
+        let final_path = self.write_to_unique_path(destination).await?;
+
+        if self.source == FileSource::File {
+            if let Some(path) = &self.metadata.path {
+                tokio::fs::remove_file(path).await?;
+            }
+        }
+
+        File::from_file(&final_path, None).await
+    }
+
+    /// Write the file's contents to a collision-free path derived from
+    /// `destination`, returning the path actually written.
+    async fn write_to_unique_path(&self, destination: &str) -> Result<String> {
+        if let Some(parent) = Path::new(destination).parent() {
+            if !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+
+        let mut candidate = destination.to_string();
+
+        for _ in 0..UNIQUE_SAVE_ATTEMPTS {
+            let file = match tokio::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&candidate)
+                .await
+            {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    candidate = unique_candidate_path(destination);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            drop(file);
+
+            self.write_to_path(&candidate).await?;
+            return Ok(candidate);
+        }
+
+        Err(FileError::InvalidSource(format!(
+            "Could not find a collision-free path for '{}' after {} attempts",
+            destination, UNIQUE_SAVE_ATTEMPTS
+        )))
+    }
+
+    /// Write the file's contents to `destination`, streaming chunk-by-chunk
+    /// so large lazy files don't need to be fully buffered first.
+    async fn write_to_path(&self, destination: &str) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut out = tokio::fs::File::create(destination).await?;
+        let mut stream = self.open_stream().await?;
+        while let Some(chunk) = stream.next().await {
+            out.write_all(&chunk?).await?;
+        }
+        out.flush().await?;
+        Ok(())
+    }
+
+    /// Delete the file from the filesystem.
+    ///
+    /// Only works for files with source `FileSource::File`.
+    pub async fn delete(&self) -> Result<()> {
+        if self.source == FileSource::File {
+            if let Some(path) = &self.metadata.path {
+                tokio::fs::remove_file(path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Checksum
+    // -----------------------------------------------------------------------
+
+    /// Calculate the SHA-256 checksum of the file contents.
+    pub async fn checksum(&self) -> Result<String> {
+        self.checksum_with(ChecksumAlgorithm::Sha256).await
+    }
+
+    /// Calculate the checksum of the file contents using the given algorithm.
+    ///
+    /// The digest is computed incrementally over [`File::read_stream`], so
+    /// lazily-loaded files are hashed without fully buffering first.
+    pub async fn checksum_with(&self, algo: ChecksumAlgorithm) -> Result<String> {
+        Ok(hex::encode(self.hash_with(algo).await?))
+    }
+
+    /// Incrementally hash the file contents with the given algorithm.
+    async fn hash_with(&self, algo: ChecksumAlgorithm) -> Result<Vec<u8>> {
+        self.check_not_expired()?;
+        let mut hasher = algo.hasher();
+        let mut stream = self.open_stream().await?;
+        while let Some(chunk) = stream.next().await {
+            hasher.update(&chunk?);
+        }
+        Ok(hasher.finalize().to_vec())
+    }
+
+    /// Resolve a recorded hash (an ETag or `Content-MD5` header value) into
+    /// the checksum algorithm to verify with and the expected raw digest
+    /// bytes, for [`File::verify_integrity`]/[`File::assert_integrity`].
+    ///
+    /// Returns `Ok(None)` for a multipart-upload ETag (one with a
+    /// `-<part-count>` suffix), which isn't a digest of the object body and
+    /// can't be verified.
+    fn resolve_integrity_check(hash: &str) -> Result<Option<(ChecksumAlgorithm, Vec<u8>)>> {
+        if hash.contains('-') {
+            return Ok(None);
+        }
+
+        // Plain-hex ETag/digest: length identifies the algorithm (MD5, SHA-1,
+        // SHA-256, or SHA-512).
+        if hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            let algo = match hash.len() {
+                32 => Some(ChecksumAlgorithm::Md5),
+                40 => Some(ChecksumAlgorithm::Sha1),
+                64 => Some(ChecksumAlgorithm::Sha256),
+                128 => Some(ChecksumAlgorithm::Sha512),
+                _ => None,
+            };
+            if let Some(algo) = algo {
+                let expected = hex::decode(hash)
+                    .map_err(|e| FileError::InvalidSource(format!("invalid hex digest: {}", e)))?;
+                return Ok(Some((algo, expected)));
+            }
+        }
+
+        // Content-MD5 header: base64-encoded 128-bit digest.
+        if let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(hash) {
+            if decoded.len() == 16 {
+                return Ok(Some((ChecksumAlgorithm::Md5, decoded)));
+            }
+        }
+
+        Err(FileError::InvalidSource(format!(
+            "Unrecognized hash format, cannot verify: {}",
+            hash
+        )))
+    }
+
+    /// Verify that the file's contents match the checksum recorded in its metadata
+    /// (typically an S3/HTTP ETag or `Content-MD5` header captured at load time).
+    ///
+    /// Recognizes a plain-hex MD5/SHA-1/SHA-256/SHA-512 ETag as well as a
+    /// base64-encoded `Content-MD5` header. A multipart-upload ETag (one
+    /// with a `-<part-count>` suffix) is not a digest of the object body and
+    /// cannot be verified; this returns `Ok(false)` in that case.
+    pub async fn verify_integrity(&self) -> Result<bool> {
+        let hash = self.metadata.hash.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("File has no recorded hash to verify against".to_string())
+        })?;
+
+        let Some((algo, expected)) = Self::resolve_integrity_check(hash)? else {
+            return Ok(false);
+        };
+
+        Ok(self.hash_with(algo).await? == expected)
+    }
+
+    /// Verify the file's contents against its recorded source hash (see
+    /// [`File::verify_integrity`]), returning a typed
+    /// [`FileError::ChecksumMismatch`] instead of `Ok(false)` on a mismatch.
+    ///
+    /// Intended as an optional post-download integrity check for
+    /// [`FileSource::Url`]/[`FileSource::S3`] files whose server returned a
+    /// strong content-hash ETag: call this after fetching to catch a
+    /// corrupted or tampered download before using the contents. See
+    /// [`File::from_url_verified`] to run this automatically on fetch.
+    pub async fn assert_integrity(&self) -> Result<()> {
+        let hash = self.metadata.hash.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("File has no recorded hash to verify against".to_string())
+        })?;
+
+        let Some((algo, expected)) = Self::resolve_integrity_check(hash)? else {
+            return Err(FileError::InvalidSource(
+                "Multipart-upload ETags are not a digest of the object body and cannot be verified"
+                    .to_string(),
+            ));
+        };
+
+        let actual = self.hash_with(algo).await?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(FileError::ChecksumMismatch {
+                expected: hex::encode(expected),
+                actual: hex::encode(actual),
+                algorithm: format!("{:?}", algo).to_lowercase(),
+            })
+        }
+    }
+
+    /// Verify that the file's SHA-256 checksum matches `expected` (a hex
+    /// digest, case-insensitive).
+    ///
+    /// Unlike [`File::verify_integrity`], this compares against a caller-supplied
+    /// digest rather than whatever validator the source happened to report, so it
+    /// works for any source and isn't defeated by a multipart-upload ETag.
+    pub async fn verify_checksum(&self, expected: &str) -> Result<bool> {
+        let actual = self.checksum().await?;
+        Ok(actual.eq_ignore_ascii_case(expected))
+    }
+
+    /// The file's SHA-256 digest as lowercase hex, suitable for use as a
+    /// content-addressed filename or storage key.
+    ///
+    /// Equivalent to `checksum()`, named for this use case.
+    pub async fn content_address(&self) -> Result<String> {
+        self.checksum().await
+    }
+
+    /// Save the file into `dir` under its [`File::content_address`] (the
+    /// file's SHA-256 hex digest plus its extension, if any), deduplicating
+    /// automatically when identical content is already stored there.
+    ///
+    /// The checksum is computed once and reused both as the filename and for
+    /// the dedup check: if `dir/{checksum}{ext}` already exists and its size
+    /// matches this file's, the write is skipped entirely and the existing
+    /// file is returned. See [`crate::content_store::ContentStore::get`] for
+    /// the inverse lookup.
+    pub async fn save_by_checksum(&self, dir: &str) -> Result<(File, File)> {
+        let checksum = self.checksum().await?;
+
+        let mut filename = checksum;
+        if let Some(ext) = &self.metadata.extension {
+            filename.push('.');
+            filename.push_str(ext);
+        }
+        let destination = Path::new(dir).join(&filename);
+        let destination = destination.to_str().ok_or_else(|| {
+            FileError::InvalidSource(format!("'{}' is not valid UTF-8", destination.display()))
+        })?;
+
+        if let (Ok(existing), Some(size)) =
+            (tokio::fs::metadata(destination).await, self.metadata.size)
+        {
+            if existing.len() == size {
+                let existing_file = File::from_file(destination, None).await?;
+                let original = File {
+                    source: self.source,
+                    data: self.data.clone(),
+                    metadata: self.metadata.clone(),
+                };
+                return Ok((original, existing_file));
+            }
+        }
+
+        tokio::fs::create_dir_all(dir).await?;
+        self.save(destination).await
+    }
+
+    // -----------------------------------------------------------------------
+    // HTTP upload
+    // -----------------------------------------------------------------------
+
+    /// Upload the file's contents to a remote HTTP endpoint, streaming the
+    /// body instead of buffering it in memory first.
+    ///
+    /// The mirror of [`File::from_url`] for the outbound direction:
+    /// `Content-Type` is set from [`File::mime_type`], `Content-Length` from
+    /// [`File::size`] when known, and `Content-Disposition` from
+    /// [`File::name`], the same way [`File::upload_to_s3_with_client`] sets
+    /// them for S3. `method` is an HTTP method name such as `"PUT"` or
+    /// `"POST"` (case-insensitive).
+    pub async fn upload(&self, url: &str, method: &str) -> Result<UploadResponse> {
+        self.check_not_expired()?;
+
+        let method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|e| FileError::InvalidSource(format!("invalid HTTP method: {}", e)))?;
+
+        let stream = self.open_stream().await?;
+        let body = reqwest::Body::wrap_stream(stream);
+
+        let mut req = reqwest::Client::new().request(method, url).body(body);
+
+        if let Some(mime) = &self.metadata.mime_type {
+            req = req.header(reqwest::header::CONTENT_TYPE, mime);
+        }
+        if let Some(size) = self.metadata.size {
+            req = req.header(reqwest::header::CONTENT_LENGTH, size);
+        }
+        if let Some(name) = &self.metadata.name {
+            req = req.header(
+                reqwest::header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", name),
+            );
+        }
+
+        let response = req.send().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        Ok(UploadResponse { status, headers })
+    }
+
+    // -----------------------------------------------------------------------
+    // Compression
+    // -----------------------------------------------------------------------
+
+    /// Compress the file's contents with the given algorithm.
+    ///
+    /// Returns a new `File` whose `mime_type` becomes `application/zstd` or
+    /// `application/gzip` and whose `name`/`extension` gain the matching
+    /// suffix. Encoding is CPU-bound, so it runs on a blocking thread via
+    /// `tokio::task::spawn_blocking`.
+    pub async fn compress(&self, algo: CompressionAlgorithm) -> Result<File> {
+        let data = self.read().await?;
+        let compressed = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+            match algo {
+                CompressionAlgorithm::Zstd => Ok(zstd::stream::encode_all(data.as_ref(), 0)?),
+                CompressionAlgorithm::Gzip => {
+                    use std::io::Write;
+                    let mut encoder =
+                        flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                    encoder.write_all(&data)?;
+                    Ok(encoder.finish()?)
+                }
+            }
+        })
+        .await
+        .map_err(|e| FileError::InvalidSource(format!("Compression task panicked: {}", e)))??;
+
+        let mut metadata = self.metadata.clone();
+        metadata.mime_type = Some(algo.mime_type().to_string());
+        metadata.extension = Some(algo.extension().to_string());
+        metadata.name = metadata
+            .name
+            .map(|name| format!("{}.{}", name, algo.extension()));
+        metadata.size = Some(compressed.len() as u64);
+
+        Ok(File {
+            source: FileSource::Bytes,
+            data: FileData::Buffered(Bytes::from(compressed)),
+            metadata,
+        })
+    }
+
+    /// Decompress the file's contents, auto-detecting Zstd vs. Gzip from the
+    /// magic bytes via [`detect_from_bytes`].
+    ///
+    /// Detection re-runs on the decompressed output so the resulting `File`
+    /// reports its true inner MIME type and extension.
+    pub async fn decompress(&self) -> Result<File> {
+        let data = self.read().await?;
+        let detection = detect_from_bytes(&data, self.metadata.name.as_deref());
+
+        let decompressed = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+            match detection.mime_type.as_deref() {
+                Some("application/zstd") => Ok(zstd::stream::decode_all(data.as_ref())?),
+                Some("application/gzip") | Some("application/x-gzip") => {
+                    use std::io::Read;
+                    let mut decoder = flate2::read::GzDecoder::new(data.as_ref());
+                    let mut out = Vec::new();
+                    decoder.read_to_end(&mut out)?;
+                    Ok(out)
+                }
+                _ => Err(FileError::InvalidSource(
+                    "File does not appear to be Zstd- or Gzip-compressed".to_string(),
+                )),
+            }
+        })
+        .await
+        .map_err(|e| FileError::InvalidSource(format!("Decompression task panicked: {}", e)))??;
+
+        let mut metadata = Metadata::new();
+        metadata.name = self.metadata.name.clone();
+        metadata.size = Some(decompressed.len() as u64);
+
+        let inner_detection = detect_from_bytes(&decompressed, metadata.name.as_deref());
+        metadata.mime_type = inner_detection.mime_type;
+        metadata.extension = inner_detection.extension;
+
+        Ok(File {
+            source: FileSource::Bytes,
+            data: FileData::Buffered(Bytes::from(decompressed)),
+            metadata,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Archives
+    // -----------------------------------------------------------------------
+
+    /// Seed a `File` from a single parsed tar entry.
+    ///
+    /// The entry's archive path becomes the file's `name` and `path`, its
+    /// size and mtime seed `Metadata`, and MIME type/extension are filled in
+    /// by the usual detection in [`File::from_bytes`]. Used by both
+    /// [`File::tar_entries`] and [`File::tar_entries_stream`].
+    pub async fn from_tar_entry(entry: ArchiveEntry) -> Result<File> {
+        let hint = MetadataHint {
+            name: Some(entry.path.clone()),
+            path: Some(entry.path),
+            size: Some(entry.size),
+            last_modified: entry.mtime,
+            ..Default::default()
+        };
+        File::from_bytes(entry.data, Some(hint)).await
+    }
+
+    /// Parse this file as a tar archive, returning one `File` per member entry.
+    ///
+    /// Buffers the whole archive and every member's contents before
+    /// returning; see [`File::tar_entries_stream`] for a variant that
+    /// processes one member at a time instead.
+    pub async fn tar_entries(&self) -> Result<Vec<File>> {
+        let data = self.read().await?;
+        let entries = tokio::task::spawn_blocking(move || read_tar_entries(&data))
+            .await
+            .map_err(|e| FileError::InvalidSource(format!("Tar parsing task panicked: {}", e)))??;
+
+        let mut files = Vec::with_capacity(entries.len());
+        for entry in entries {
+            files.push(File::from_tar_entry(entry).await?);
+        }
+
+        Ok(files)
+    }
+
+    /// Parse this file as a tar archive, yielding one `File` per member entry
+    /// as a stream instead of collecting them all into a `Vec` up front.
+    ///
+    /// The archive's tar headers and member bodies are parsed on a blocking
+    /// task and sent over a channel (holding up to four entries) as each one
+    /// is read, so only a few members' bytes are buffered at a time; this also
+    /// handles concatenated archives and trailing zero blocks the way
+    /// [`File::tar_entries`] does. The archive itself is still read into
+    /// memory up front via [`File::read`].
+    pub async fn tar_entries_stream(&self) -> Result<FileStream> {
+        let data = self.read().await?;
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<Result<ArchiveEntry>>(4);
+
+        tokio::task::spawn_blocking(move || {
+            let result = visit_tar_entries(data.as_ref(), |entry| {
+                tx.blocking_send(Ok(entry)).map_err(|_| {
+                    FileError::InvalidSource("tar entry receiver was dropped".to_string())
+                })
+            });
+            if let Err(e) = result {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+
+        Ok(Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+            let entry = rx.recv().await?;
+            let file = match entry {
+                Ok(entry) => File::from_tar_entry(entry).await,
+                Err(e) => Err(e),
+            };
+            Some((file, rx))
+        })))
+    }
+
+    /// Build a tar archive `File` from a set of input files, using each
+    /// file's `name` as its archive path and `size`/`last_modified` for its
+    /// header. Optionally compresses the resulting archive.
+    pub async fn from_tar(files: &[File], compression: Option<CompressionAlgorithm>) -> Result<File> {
+        let mut inputs = Vec::with_capacity(files.len());
+        for file in files {
+            let path = file.name().ok_or_else(|| {
+                FileError::InvalidSource("File has no name to use as a tar entry path".to_string())
+            })?;
+            inputs.push(ArchiveInput {
+                path: path.to_string(),
+                mtime: file.last_modified(),
+                data: file.read().await?,
+            });
+        }
+
+        let tar_bytes = tokio::task::spawn_blocking(move || write_tar(inputs))
+            .await
+            .map_err(|e| FileError::InvalidSource(format!("Tar writing task panicked: {}", e)))??;
+
+        let hint = MetadataHint {
+            mime_type: Some("application/x-tar".to_string()),
+            extension: Some("tar".to_string()),
+            ..Default::default()
+        };
+        let archive = File::from_bytes(Bytes::from(tar_bytes), Some(hint)).await?;
+
+        match compression {
+            Some(algo) => archive.compress(algo).await,
+            None => Ok(archive),
+        }
+    }
+
+    /// Decompress this file if it is Gzip/Zstd-wrapped, returning the raw
+    /// bytes and the inner MIME type to use for archive-kind detection.
+    ///
+    /// A private, non-recursive helper: [`File::decompress`] can't be called
+    /// recursively from an async fn without boxing the resulting future, so
+    /// this calls it at most once and hands back plain data instead.
+    async fn decompressed_for_archive(&self) -> Result<(Bytes, Option<String>)> {
+        match self.decompress().await {
+            Ok(inner) => {
+                let mime_type = inner.mime_type().map(|s| s.to_string());
+                Ok((inner.read().await?, mime_type))
+            }
+            Err(_) => Ok((self.read().await?, self.mime_type().map(|s| s.to_string()))),
+        }
+    }
+
+    /// List a tar or zip archive's member entries, auto-decompressing a
+    /// Gzip/Zstd-wrapped archive first. Zip members are listed without
+    /// reading their contents; tar members are read in full while the
+    /// archive is walked.
+    pub async fn list_archive(&self) -> Result<Vec<ArchiveEntryInfo>> {
+        let (data, mime_type) = self.decompressed_for_archive().await?;
+        let kind = detect_archive_kind(mime_type.as_deref(), &data).ok_or_else(|| {
+            FileError::InvalidSource("File does not appear to be a tar or zip archive".to_string())
+        })?;
+
+        tokio::task::spawn_blocking(move || list_entries(kind, &data))
+            .await
+            .map_err(|e| FileError::InvalidSource(format!("Archive listing task panicked: {}", e)))?
+    }
+
+    /// Extract a single named entry from a tar or zip archive as a `File`,
+    /// auto-decompressing a Gzip/Zstd-wrapped archive first.
+    ///
+    /// Rejects entries whose path would escape the archive root and caps
+    /// extraction at [`DEFAULT_MAX_ENTRY_SIZE`] bytes.
+    pub async fn extract_entry(&self, name: &str) -> Result<File> {
+        let (data, mime_type) = self.decompressed_for_archive().await?;
+        let kind = detect_archive_kind(mime_type.as_deref(), &data).ok_or_else(|| {
+            FileError::InvalidSource("File does not appear to be a tar or zip archive".to_string())
+        })?;
+
+        let entry_name = name.to_string();
+        let extracted = tokio::task::spawn_blocking(move || {
+            extract_entry(kind, &data, &entry_name, DEFAULT_MAX_ENTRY_SIZE)
+        })
+        .await
+        .map_err(|e| FileError::InvalidSource(format!("Archive extraction task panicked: {}", e)))??;
+
+        let hint = MetadataHint {
+            name: Some(name.to_string()),
+            path: Some(name.to_string()),
+            ..Default::default()
+        };
+        File::from_bytes(extracted, Some(hint)).await
+    }
+
+    // -----------------------------------------------------------------------
+    // S3 operations
+    // -----------------------------------------------------------------------
+
+    /// Upload the file to an S3 bucket.
+    pub async fn upload_to_s3(&self, bucket: &str, key: &str) -> Result<()> {
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let client = S3Client::new(&config);
+        self.upload_to_s3_with_client(&client, bucket, key).await
+    }
+
+    /// Upload the file to an S3 bucket using a provided client.
+    pub async fn upload_to_s3_with_client(
+        &self,
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+    ) -> Result<()> {
+        let data = self.read().await?;
+        let mut req = client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(data.into());
+
+        if let Some(mime) = &self.metadata.mime_type {
+            req = req.content_type(mime.clone());
+        }
+        if let Some(size) = self.metadata.size {
+            req = req.content_length(size as i64);
+        }
+        if let Some(name) = &self.metadata.name {
+            req = req.content_disposition(format!("attachment; filename=\"{}\"", name));
+        }
+
+        req.send().await.map_err(|e| FileError::S3(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Upload the file to a bucket/key via a backend-agnostic [`ObjectStore`].
+    pub async fn upload_to_object_store(
+        &self,
+        store: &dyn ObjectStore,
+        bucket: &str,
+        key: &str,
+    ) -> Result<()> {
+        let data = self.read().await?;
+        store
+            .put(bucket, key, data, self.metadata.mime_type.as_deref())
+            .await
+    }
+
+    /// Upload the file to S3 using a multipart upload, streaming the file's body
+    /// from [`File::open_stream`] (so this also covers `Stream`-sourced files
+    /// without fully buffering them) and uploading each `part_size`-byte part
+    /// (the final part may be smaller, clamped to the S3 minimum otherwise) as
+    /// soon as it's assembled, up to 4 parts concurrently. `part_size` defaults
+    /// to 8 MiB when `None`.
+    ///
+    /// On any part failure the in-progress upload is aborted via
+    /// `AbortMultipartUpload` so no orphaned storage charges accrue. Returns
+    /// the final object's metadata (size and ETag) on success. Returns
+    /// [`FileError::Expired`] if [`File::is_expired`] is true.
+    pub async fn upload_to_s3_multipart(
+        &self,
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        part_size: Option<usize>,
+    ) -> Result<Metadata> {
+        self.check_not_expired()?;
+        const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+        const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+        const MAX_CONCURRENT_PARTS: usize = 4;
+
+        let part_size = part_size.unwrap_or(DEFAULT_PART_SIZE).max(MIN_PART_SIZE);
+
+        let mut create_req = client.create_multipart_upload().bucket(bucket).key(key);
+        if let Some(mime) = &self.metadata.mime_type {
+            create_req = create_req.content_type(mime.clone());
+        }
+        if let Some(name) = &self.metadata.name {
+            create_req = create_req.content_disposition(format!("attachment; filename=\"{}\"", name));
+        }
+
+        let create_resp = create_req
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
+        let upload_id = create_resp
+            .upload_id()
+            .ok_or_else(|| FileError::S3("Missing upload_id from CreateMultipartUpload".to_string()))?
+            .to_string();
+
+        let mut uploads = FuturesUnordered::new();
+        let mut completed: Vec<aws_sdk_s3::types::CompletedPart> = Vec::new();
+        let mut total_size: u64 = 0;
+        let mut next_part_number: i32 = 1;
+        let mut buffer: Vec<u8> = Vec::with_capacity(part_size);
+
+        let chunking_result: Result<()> = async {
+            // Queue a part as soon as it's assembled, pulled off the stream one
+            // chunk at a time, instead of collecting the whole body into a `Vec`
+            // first. At most `MAX_CONCURRENT_PARTS` parts are buffered/in-flight
+            // at once: once that many are queued, pulling more out of the stream
+            // waits for one of the in-flight uploads to finish first.
+            macro_rules! queue_part {
+                ($part_bytes:expr) => {{
+                    if uploads.len() >= MAX_CONCURRENT_PARTS {
+                        if let Some(result) = uploads.next().await {
+                            let (part_number, etag) = result?;
+                            completed.push(
+                                aws_sdk_s3::types::CompletedPart::builder()
+                                    .part_number(part_number)
+                                    .e_tag(etag)
+                                    .build(),
+                            );
+                        }
+                    }
+
+                    let part_bytes = $part_bytes;
+                    total_size += part_bytes.len() as u64;
+                    let part_number = next_part_number;
+                    next_part_number += 1;
+                    uploads.push(upload_part(client, bucket, key, &upload_id, part_number, part_bytes));
+                }};
+            }
+
+            let mut stream = self.open_stream().await?;
+            while let Some(chunk) = stream.next().await {
+                buffer.extend_from_slice(&chunk?);
+                while buffer.len() >= part_size {
+                    let part_bytes = Bytes::copy_from_slice(&buffer[..part_size]);
+                    buffer.drain(..part_size);
+                    queue_part!(part_bytes);
+                }
+            }
+
+            if !buffer.is_empty() {
+                queue_part!(Bytes::from(std::mem::take(&mut buffer)));
+            }
+
+            while let Some(result) = uploads.next().await {
+                let (part_number, etag) = result?;
+                completed.push(
+                    aws_sdk_s3::types::CompletedPart::builder()
+                        .part_number(part_number)
+                        .e_tag(etag)
+                        .build(),
+                );
+            }
+
+            Ok(())
+        }
+        .await;
+
+        if let Err(e) = chunking_result {
+            abort_multipart_upload(client, bucket, key, &upload_id).await;
+            return Err(e);
+        }
+
+        completed.sort_by_key(|p| p.part_number().unwrap_or(0));
+
+        let completed_upload = aws_sdk_s3::types::CompletedMultipartUpload::builder()
+            .set_parts(Some(completed))
+            .build();
+
+        let complete_result = client
+            .complete_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(completed_upload)
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()));
+
+        let complete_resp = match complete_result {
+            Ok(resp) => resp,
+            Err(e) => {
+                abort_multipart_upload(client, bucket, key, &upload_id).await;
+                return Err(e);
             }
+        };
+
+        let mut metadata = Metadata::new();
+        metadata.url = Some(format!("s3://{}/{}", bucket, key));
+        metadata.name.clone_from(&self.metadata.name);
+        metadata.mime_type.clone_from(&self.metadata.mime_type);
+        metadata.size = Some(total_size);
+        if let Some(etag) = complete_resp.e_tag() {
+            metadata.hash = Some(etag.trim_matches('"').to_string());
         }
-        Ok(())
+
+        Ok(metadata)
     }
 
-    // -----------------------------------------------------------------------
-    // Checksum
-    // -----------------------------------------------------------------------
+    /// Download a file from S3 (alias for `from_s3`).
+    pub async fn download_from_s3(bucket: &str, key: &str) -> Result<Self> {
+        Self::from_s3(bucket, key, None).await
+    }
 
-    /// Calculate the SHA-256 checksum of the file contents.
-    pub async fn checksum(&self) -> Result<String> {
-        let mut hasher = Sha256::new();
-        hasher.update(&self.data);
-        Ok(hex::encode(hasher.finalize()))
+    /// Download a file from S3 using a provided client.
+    pub async fn download_from_s3_with_client(
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+    ) -> Result<Self> {
+        Self::from_s3_with_client(client, bucket, key, None).await
     }
 
-    // -----------------------------------------------------------------------
-    // S3 operations
-    // -----------------------------------------------------------------------
+    /// Fetch the S3 object tags for this file.
+    ///
+    /// Only works for files with source `FileSource::S3`.
+    pub async fn get_tags(&self) -> Result<BTreeMap<String, String>> {
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+        let (bucket, key) = parse_s3_url(s3_url)?;
 
-    /// Upload the file to an S3 bucket.
-    pub async fn upload_to_s3(&self, bucket: &str, key: &str) -> Result<()> {
         let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
         let client = S3Client::new(&config);
-        self.upload_to_s3_with_client(&client, bucket, key).await
+
+        self.get_tags_with_client(&client, &bucket, &key).await
     }
 
-    /// Upload the file to an S3 bucket using a provided client.
-    pub async fn upload_to_s3_with_client(
+    /// Fetch the S3 object tags for this file using a provided client.
+    pub async fn get_tags_with_client(
         &self,
         client: &S3Client,
         bucket: &str,
         key: &str,
-    ) -> Result<()> {
-        let mut req = client
-            .put_object()
+    ) -> Result<BTreeMap<String, String>> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot fetch tags for a non-S3 file".to_string(),
+            ));
+        }
+
+        let resp = client
+            .get_object_tagging()
             .bucket(bucket)
             .key(key)
-            .body(self.data.clone().into());
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
 
-        if let Some(mime) = &self.metadata.mime_type {
-            req = req.content_type(mime.clone());
-        }
-        if let Some(size) = self.metadata.size {
-            req = req.content_length(size as i64);
-        }
-        if let Some(name) = &self.metadata.name {
-            req = req.content_disposition(format!("attachment; filename=\"{}\"", name));
-        }
+        Ok(resp
+            .tag_set()
+            .iter()
+            .map(|t| (t.key().to_string(), t.value().to_string()))
+            .collect())
+    }
 
-        req.send().await.map_err(|e| FileError::S3(e.to_string()))?;
+    /// Replace the S3 object tags for this file.
+    ///
+    /// Only works for files with source `FileSource::S3`.
+    pub async fn set_tags(&self, tags: &BTreeMap<String, String>) -> Result<()> {
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+        let (bucket, key) = parse_s3_url(s3_url)?;
 
-        Ok(())
-    }
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let client = S3Client::new(&config);
 
-    /// Download a file from S3 (alias for `from_s3`).
-    pub async fn download_from_s3(bucket: &str, key: &str) -> Result<Self> {
-        Self::from_s3(bucket, key, None).await
+        self.set_tags_with_client(&client, &bucket, &key, tags).await
     }
 
-    /// Download a file from S3 using a provided client.
-    pub async fn download_from_s3_with_client(
+    /// Replace the S3 object tags for this file using a provided client.
+    pub async fn set_tags_with_client(
+        &self,
         client: &S3Client,
         bucket: &str,
         key: &str,
-    ) -> Result<Self> {
-        Self::from_s3_with_client(client, bucket, key, None).await
+        tags: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot set tags for a non-S3 file".to_string(),
+            ));
+        }
+
+        let tag_set = tags
+            .iter()
+            .map(|(k, v)| aws_sdk_s3::types::Tag::builder().key(k).value(v).build())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        let tagging = aws_sdk_s3::types::Tagging::builder()
+            .set_tag_set(Some(tag_set))
+            .build()
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        client
+            .put_object_tagging()
+            .bucket(bucket)
+            .key(key)
+            .tagging(tagging)
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        Ok(())
     }
 
     /// Generate a presigned URL for accessing an S3 object.
@@ -764,6 +2519,9 @@ impl File {
     }
 
     /// Generate a presigned URL using a provided S3 client.
+    ///
+    /// Delegates to [`File::presigned_download_url`]; see there for the
+    /// signing details and the 7-day `expires_in` cap.
     pub async fn get_signed_url_with_client(
         &self,
         client: &S3Client,
@@ -777,9 +2535,129 @@ impl File {
             ));
         }
 
-        let presigning =
-            PresigningConfig::expires_in(std::time::Duration::from_secs(expires_in_secs))
-                .map_err(|e| FileError::S3(format!("Presigning config error: {}", e)))?;
+        Self::presigned_download_url(
+            client,
+            bucket,
+            key,
+            std::time::Duration::from_secs(expires_in_secs),
+        )
+        .await
+    }
+
+    /// Generate a presigned URL for accessing this file's S3-compatible
+    /// object at a custom endpoint, per `s3_config`.
+    pub async fn get_signed_url_with_endpoint(
+        &self,
+        s3_config: &S3Config,
+        expires_in_secs: u64,
+    ) -> Result<String> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot generate signed URL for non-S3 file".to_string(),
+            ));
+        }
+
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+
+        let (bucket, key) = parse_s3_url(s3_url)?;
+        let client = s3_config.build_client().await;
+
+        self.get_signed_url_with_client(&client, &bucket, &key, expires_in_secs)
+            .await
+    }
+
+    /// Generate a presigned URL for uploading (`PutObject`) to this file's S3 location.
+    ///
+    /// Only works for files with source `FileSource::S3`.
+    pub async fn get_signed_upload_url(
+        &self,
+        expires_in_secs: u64,
+        opts: SignedUploadUrlOptions,
+    ) -> Result<String> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot generate signed upload URL for non-S3 file".to_string(),
+            ));
+        }
+
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+
+        let (bucket, key) = parse_s3_url(s3_url)?;
+
+        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+        let client = S3Client::new(&config);
+
+        self.get_signed_upload_url_with_client(&client, &bucket, &key, expires_in_secs, opts)
+            .await
+    }
+
+    /// Generate a presigned upload URL using a provided S3 client.
+    ///
+    /// Delegates to [`File::presigned_upload_url`]; see there for the
+    /// signing details and the 7-day `expires_in` cap.
+    pub async fn get_signed_upload_url_with_client(
+        &self,
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        expires_in_secs: u64,
+        opts: SignedUploadUrlOptions,
+    ) -> Result<String> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot generate signed upload URL for non-S3 file".to_string(),
+            ));
+        }
+
+        Self::presigned_upload_url(
+            client,
+            bucket,
+            key,
+            std::time::Duration::from_secs(expires_in_secs),
+            opts,
+        )
+        .await
+    }
+
+    /// Generate a presigned upload URL for this file's S3-compatible object
+    /// at a custom endpoint, per `s3_config`.
+    pub async fn get_signed_upload_url_with_endpoint(
+        &self,
+        s3_config: &S3Config,
+        expires_in_secs: u64,
+        opts: SignedUploadUrlOptions,
+    ) -> Result<String> {
+        if self.source != FileSource::S3 {
+            return Err(FileError::InvalidSource(
+                "Cannot generate signed upload URL for non-S3 file".to_string(),
+            ));
+        }
+
+        let s3_url = self.metadata.url.as_deref().ok_or_else(|| {
+            FileError::InvalidSource("S3 file is missing URL metadata".to_string())
+        })?;
+
+        let (bucket, key) = parse_s3_url(s3_url)?;
+        let client = s3_config.build_client().await;
+
+        self.get_signed_upload_url_with_client(&client, &bucket, &key, expires_in_secs, opts)
+            .await
+    }
+
+    /// Generate a presigned URL for downloading (`GetObject`) an S3 object,
+    /// valid for `expires_in`. An `expires_in` beyond the S3 maximum of 7
+    /// days is rejected with [`FileError::S3`].
+    pub async fn presigned_download_url(
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String> {
+        let presigning = presigning_config(expires_in)?;
 
         let presigned = client
             .get_object()
@@ -792,6 +2670,136 @@ impl File {
         Ok(presigned.uri().to_string())
     }
 
+    /// Generate a presigned URL for uploading (`PutObject`) an S3 object,
+    /// valid for `expires_in` and constrained by `opts` (e.g. a required
+    /// `Content-Type`). An `expires_in` beyond the S3 maximum of 7 days is
+    /// rejected with [`FileError::S3`].
+    pub async fn presigned_upload_url(
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        expires_in: std::time::Duration,
+        opts: SignedUploadUrlOptions,
+    ) -> Result<String> {
+        let presigning = presigning_config(expires_in)?;
+
+        let mut req = client.put_object().bucket(bucket).key(key);
+        if let Some(content_type) = opts.content_type {
+            req = req.content_type(content_type);
+        }
+        if let Some(content_length) = opts.content_length {
+            req = req.content_length(content_length as i64);
+        }
+        if let Some(acl) = opts.acl {
+            req = req.acl(aws_sdk_s3::types::ObjectCannedAcl::from(acl.as_str()));
+        }
+
+        let presigned = req
+            .presigned(presigning)
+            .await
+            .map_err(|e| FileError::S3(format!("Presigning error: {}", e)))?;
+
+        Ok(presigned.uri().to_string())
+    }
+
+    /// Generate the fields needed for a browser `multipart/form-data` `POST`
+    /// upload directly to S3, constrained by `conditions` (e.g.
+    /// `["content-length-range", min, max]`, `["starts-with", "$key", prefix]`,
+    /// or `{"acl": "public-read"}`), valid for `expires_in`.
+    ///
+    /// Builds a base64-encoded JSON policy document and signs it with the
+    /// SigV4 signing key (an HMAC-SHA256 chain over date/region/`s3`/`aws4_request`,
+    /// with a final HMAC over the base64 policy, hex-encoded). Unlike a plain
+    /// presigned `PUT` (see [`File::presigned_upload_url`]), this lets the
+    /// caller express constraints like size limits and key prefixes that the
+    /// browser form itself cannot violate.
+    pub async fn presigned_post(
+        client: &S3Client,
+        bucket: &str,
+        key: &str,
+        conditions: Vec<serde_json::Value>,
+        expires_in: std::time::Duration,
+    ) -> Result<PresignedPost> {
+        let region = client
+            .config()
+            .region()
+            .map(|r| r.to_string())
+            .ok_or_else(|| FileError::S3("S3 client is missing a configured region".to_string()))?;
+
+        let credentials = client
+            .config()
+            .credentials_provider()
+            .ok_or_else(|| {
+                FileError::S3("S3 client is missing a credentials provider".to_string())
+            })?
+            .provide_credentials()
+            .await
+            .map_err(|e| FileError::S3(format!("Failed to resolve credentials: {}", e)))?;
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, region);
+        let x_amz_credential = format!("{}/{}", credentials.access_key_id(), credential_scope);
+        let expiration = (now
+            + chrono::Duration::from_std(expires_in)
+                .map_err(|e| FileError::InvalidSource(e.to_string()))?)
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+
+        let mut policy_conditions = vec![
+            serde_json::json!({"bucket": bucket}),
+            serde_json::json!(["eq", "$key", key]),
+            serde_json::json!({"x-amz-algorithm": "AWS4-HMAC-SHA256"}),
+            serde_json::json!({"x-amz-credential": x_amz_credential}),
+            serde_json::json!({"x-amz-date": amz_date}),
+        ];
+        policy_conditions.extend(conditions.iter().cloned());
+        if let Some(token) = credentials.session_token() {
+            policy_conditions.push(serde_json::json!({"x-amz-security-token": token}));
+        }
+
+        let policy_doc = serde_json::json!({
+            "expiration": expiration,
+            "conditions": policy_conditions,
+        });
+        let policy_b64 = base64::engine::general_purpose::STANDARD.encode(
+            serde_json::to_vec(&policy_doc)
+                .map_err(|e| FileError::InvalidSource(e.to_string()))?,
+        );
+
+        let signature = sign_post_policy(credentials.secret_access_key(), &date_stamp, &region, &policy_b64);
+
+        let mut fields = std::collections::HashMap::new();
+        fields.insert("key".to_string(), key.to_string());
+        fields.insert("policy".to_string(), policy_b64);
+        fields.insert("x-amz-algorithm".to_string(), "AWS4-HMAC-SHA256".to_string());
+        fields.insert("x-amz-credential".to_string(), x_amz_credential);
+        fields.insert("x-amz-date".to_string(), amz_date);
+        fields.insert("x-amz-signature".to_string(), signature);
+        if let Some(token) = credentials.session_token() {
+            fields.insert("x-amz-security-token".to_string(), token.to_string());
+        }
+
+        // Echo user-supplied object-form conditions (e.g. {"acl": "public-read"})
+        // as form fields; array-form conditions (content-length-range,
+        // starts-with) constrain the upload but aren't themselves form fields.
+        for condition in &conditions {
+            if let Some(obj) = condition.as_object() {
+                for (k, v) in obj {
+                    if let Some(s) = v.as_str() {
+                        fields.entry(k.clone()).or_insert_with(|| s.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(PresignedPost {
+            url: format!("https://{}.s3.{}.amazonaws.com", bucket, region),
+            fields,
+        })
+    }
+
     // -----------------------------------------------------------------------
     // Metadata mutation
     // -----------------------------------------------------------------------
@@ -825,19 +2833,53 @@ impl File {
         if updates.created_at.is_some() {
             self.metadata.created_at = updates.created_at;
         }
+        if updates.version.is_some() {
+            self.metadata.version = updates.version;
+        }
+        if updates.expires_at.is_some() {
+            self.metadata.expires_at = updates.expires_at;
+        }
+    }
+
+    /// Set the file's expiry deadline to a human-friendly duration from now
+    /// (e.g. `"5ms"`, `"30s"`, `"2h"`, `"30d"`) — see
+    /// [`crate::metadata::parse_expire_directive`] for the supported units.
+    pub fn set_expire_in(&mut self, directive: &str) -> Result<()> {
+        self.metadata.expires_at = Some(Utc::now() + crate::metadata::parse_expire_directive(directive)?);
+        Ok(())
+    }
+
+    /// Clear the file's expiry deadline.
+    ///
+    /// [`File::set_metadata`] can set `expires_at` (since
+    /// `MetadataHint::expires_at: Some(_)` is copied over like any other
+    /// field) but, like the rest of its fields, can't distinguish "leave
+    /// alone" from "clear" for a bare `None`; this is the dedicated way to
+    /// clear it.
+    pub fn clear_expiry(&mut self) {
+        self.metadata.expires_at = None;
     }
 
     /// Returns a JSON string representation of the file metadata and source.
     pub fn to_string_pretty(&self) -> String {
+        self.to_string_pretty_with_tags(None)
+    }
+
+    /// Serialize the file's metadata to a JSON string, optionally including
+    /// S3 object tags (e.g. fetched via [`File::get_tags`]) under a `tags` key.
+    pub fn to_string_pretty_with_tags(&self, tags: Option<&BTreeMap<String, String>>) -> String {
         #[derive(serde::Serialize)]
         struct FileRepr<'a> {
             source: &'a FileSource,
             #[serde(flatten)]
             metadata: &'a Metadata,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            tags: Option<&'a BTreeMap<String, String>>,
         }
         let repr = FileRepr {
             source: &self.source,
             metadata: &self.metadata,
+            tags,
         };
         serde_json::to_string(&repr).unwrap_or_default()
     }
@@ -854,7 +2896,7 @@ impl std::fmt::Debug for File {
         f.debug_struct("File")
             .field("source", &self.source)
             .field("metadata", &self.metadata)
-            .field("data_len", &self.data.len())
+            .field("data_len", &self.data.known_len())
             .finish()
     }
 }
@@ -863,6 +2905,38 @@ impl std::fmt::Debug for File {
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Decode a `Content-Encoding`-compressed HTTP response body.
+///
+/// Unrecognized encodings (and `identity`) are passed through unchanged, on
+/// the theory that a server advertising an encoding this crate doesn't
+/// understand has presumably sent bytes the caller can still use as-is.
+fn decode_content_encoding(encoding: &str, data: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    match encoding {
+        "gzip" | "x-gzip" => {
+            let mut decoder = flate2::read::GzDecoder::new(data);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out)?;
+            Ok(out)
+        }
+        "deflate" => {
+            let mut decoder = flate2::read::ZlibDecoder::new(data);
+            let mut out = Vec::new();
+            decoder.read_to_end(&mut out)?;
+            Ok(out)
+        }
+        "br" => {
+            let mut out = Vec::new();
+            brotli::BrotliDecompress(&mut std::io::Cursor::new(data), &mut out)
+                .map_err(|e| FileError::InvalidSource(format!("Brotli decode error: {}", e)))?;
+            Ok(out)
+        }
+        "zstd" => Ok(zstd::stream::decode_all(data)?),
+        _ => Ok(data.to_vec()),
+    }
+}
+
 /// Extract a filename from a URL path.
 fn get_filename_from_url(url: &str) -> Option<String> {
     let parsed = url::Url::parse(url).ok()?;
@@ -875,6 +2949,99 @@ fn get_filename_from_url(url: &str) -> Option<String> {
     }
 }
 
+/// The number of collision-retry attempts [`File::save_unique`] and
+/// [`File::move_to_unique`] make before giving up.
+const UNIQUE_SAVE_ATTEMPTS: u32 = 16;
+
+/// Rewrite `destination`'s stem with a fresh random `-0x{16 hex digits}`
+/// suffix, replacing any such suffix already present so repeated collisions
+/// don't stack suffixes (e.g. `report-0x1234....txt`, not
+/// `report-0x1234...-0xabcd....txt`).
+fn unique_candidate_path(destination: &str) -> String {
+    let path = Path::new(destination);
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let extension = path.extension().and_then(|e| e.to_str());
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(destination);
+    let stem = strip_unique_suffix(stem);
+
+    let mut name = format!("{}-0x{}", stem, random_hex_suffix());
+    if let Some(ext) = extension {
+        name.push('.');
+        name.push_str(ext);
+    }
+
+    match parent {
+        Some(parent) => parent.join(name).to_string_lossy().into_owned(),
+        None => name,
+    }
+}
+
+/// Strip a trailing `-0x` followed by exactly 16 lowercase hex digits, if present.
+fn strip_unique_suffix(stem: &str) -> &str {
+    if let Some(idx) = stem.rfind("-0x") {
+        let suffix = &stem[idx + 3..];
+        if suffix.len() == 16 && suffix.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) {
+            return &stem[..idx];
+        }
+    }
+    stem
+}
+
+/// Generate 16 random lowercase hex digits for a unique-path suffix.
+fn random_hex_suffix() -> String {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    (0..16)
+        .map(|_| std::char::from_digit(rng.gen_range(0..16), 16).unwrap())
+        .collect()
+}
+
+/// Subslice `data` to `[start, start + len)`, clamped to the data's actual
+/// bounds (requesting past the end yields whatever bytes remain, or empty).
+fn slice_range(data: &Bytes, start: u64, len: u64) -> Bytes {
+    let start = (start as usize).min(data.len());
+    let end = start.saturating_add(len as usize).min(data.len());
+    data.slice(start..end)
+}
+
+/// The maximum lifetime S3 allows for a presigned URL.
+const S3_MAX_PRESIGN_DURATION: std::time::Duration = std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Build a `PresigningConfig`, rejecting `expires_in` values beyond the S3 maximum of 7 days.
+fn presigning_config(expires_in: std::time::Duration) -> Result<PresigningConfig> {
+    if expires_in > S3_MAX_PRESIGN_DURATION {
+        return Err(FileError::S3(format!(
+            "expires_in ({:?}) exceeds the S3 maximum of 7 days",
+            expires_in
+        )));
+    }
+    PresigningConfig::expires_in(expires_in)
+        .map_err(|e| FileError::S3(format!("Presigning config error: {}", e)))
+}
+
+/// Derive the SigV4 signing key (an HMAC-SHA256 chain over
+/// date/region/`s3`/`aws4_request`) and sign the base64-encoded POST policy
+/// with it, returning a hex-encoded signature.
+fn sign_post_policy(secret_key: &str, date_stamp: &str, region: &str, policy_b64: &str) -> String {
+    fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+        let mut mac = hmac::Hmac::<Sha256>::new_from_slice(key)
+            .expect("HMAC accepts a key of any length");
+        hmac::Mac::update(&mut mac, data.as_bytes());
+        hmac::Mac::finalize(mac).into_bytes().to_vec()
+    }
+
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date_stamp);
+    let k_region = hmac_sha256(&k_date, region);
+    let k_service = hmac_sha256(&k_region, "s3");
+    let k_signing = hmac_sha256(&k_service, "aws4_request");
+    let signature = hmac_sha256(&k_signing, policy_b64);
+
+    hex::encode(signature)
+}
+
 /// Parse an `s3://bucket/key` URL into (bucket, key).
 fn parse_s3_url(url: &str) -> Result<(String, String)> {
     let without_scheme = url
@@ -898,6 +3065,45 @@ fn parse_s3_url(url: &str) -> Result<(String, String)> {
     Ok((bucket, key))
 }
 
+/// Upload a single part of a multipart upload and return its part number and ETag.
+async fn upload_part(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+    part_number: i32,
+    chunk: Bytes,
+) -> Result<(i32, String)> {
+    let resp = client
+        .upload_part()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .part_number(part_number)
+        .body(chunk.into())
+        .send()
+        .await
+        .map_err(|e| FileError::S3(e.to_string()))?;
+
+    let etag = resp
+        .e_tag()
+        .ok_or_else(|| FileError::S3(format!("Missing ETag for part {}", part_number)))?
+        .to_string();
+
+    Ok((part_number, etag))
+}
+
+/// Abort an in-progress multipart upload, best-effort, to avoid orphaned storage charges.
+async fn abort_multipart_upload(client: &S3Client, bucket: &str, key: &str, upload_id: &str) {
+    let _ = client
+        .abort_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .send()
+        .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -929,6 +3135,22 @@ mod tests {
         assert!(parse_s3_url("s3:///key").is_err());
     }
 
+    #[test]
+    fn test_sign_post_policy_produces_hex_signature() {
+        let signature = sign_post_policy("secret", "20130824", "us-east-1", "eyJ0ZXN0IjoidmFsdWUifQ==");
+        assert_eq!(signature.len(), 64);
+        assert!(signature.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn test_sign_post_policy_is_deterministic_and_key_sensitive() {
+        let sig1 = sign_post_policy("secret", "20130824", "us-east-1", "policy");
+        let sig2 = sign_post_policy("secret", "20130824", "us-east-1", "policy");
+        let sig3 = sign_post_policy("other-secret", "20130824", "us-east-1", "policy");
+        assert_eq!(sig1, sig2);
+        assert_ne!(sig1, sig3);
+    }
+
     #[tokio::test]
     async fn test_from_bytes_basic() {
         let data = Bytes::from("hello world");
@@ -989,6 +3211,215 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_checksum_with_md5() {
+        let data = Bytes::from(vec![0u8; 8]);
+        let file = File::from_bytes(data, None).await.unwrap();
+        let checksum = file.checksum_with(ChecksumAlgorithm::Md5).await.unwrap();
+        assert_eq!(checksum.len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_plain_md5_etag() {
+        let data = Bytes::from("hello world");
+        let md5_hex = hex::encode(Md5::digest(&data));
+        let hint = Metadata {
+            hash: Some(md5_hex),
+            ..Default::default()
+        };
+        let file = File::from_bytes(data, Some(hint)).await.unwrap();
+        assert!(file.verify_integrity().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_content_md5_base64() {
+        let data = Bytes::from("hello world");
+        let md5_b64 = base64::engine::general_purpose::STANDARD.encode(Md5::digest(&data));
+        let hint = Metadata {
+            hash: Some(md5_b64),
+            ..Default::default()
+        };
+        let file = File::from_bytes(data, Some(hint)).await.unwrap();
+        assert!(file.verify_integrity().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_multipart_etag_unverifiable() {
+        let data = Bytes::from("hello world");
+        let hint = Metadata {
+            hash: Some("deadbeefdeadbeefdeadbeefdeadbeef-3".to_string()),
+            ..Default::default()
+        };
+        let file = File::from_bytes(data, Some(hint)).await.unwrap();
+        assert!(!file.verify_integrity().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_no_hash_errors() {
+        let data = Bytes::from("hello world");
+        let file = File::from_bytes(data, None).await.unwrap();
+        assert!(file.verify_integrity().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_assert_integrity_ok_and_mismatch() {
+        let data = Bytes::from("hello world");
+        let digest = hex::encode(Md5::digest(&data));
+
+        let matching = File::from_bytes(
+            data.clone(),
+            Some(Metadata {
+                hash: Some(digest),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(matching.assert_integrity().await.is_ok());
+
+        let mismatched = File::from_bytes(
+            data,
+            Some(Metadata {
+                hash: Some("deadbeefdeadbeefdeadbeefdeadbeef".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(mismatched.assert_integrity().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_recognizes_sha1_sha256_sha512_digests() {
+        let data = Bytes::from("hello world");
+
+        let sha1_hex = hex::encode(Sha1::digest(&data));
+        let file = File::from_bytes(
+            data.clone(),
+            Some(Metadata {
+                hash: Some(sha1_hex),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(file.verify_integrity().await.unwrap());
+
+        let sha256_hex = hex::encode(Sha256::digest(&data));
+        let file = File::from_bytes(
+            data.clone(),
+            Some(Metadata {
+                hash: Some(sha256_hex),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(file.verify_integrity().await.unwrap());
+
+        let sha512_hex = hex::encode(Sha512::digest(&data));
+        let file = File::from_bytes(
+            data,
+            Some(Metadata {
+                hash: Some(sha512_hex),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(file.verify_integrity().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_assert_integrity_mismatch_returns_checksum_mismatch_error() {
+        let data = Bytes::from("hello world");
+        let file = File::from_bytes(
+            data,
+            Some(Metadata {
+                hash: Some("deadbeefdeadbeefdeadbeefdeadbeef".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        match file.assert_integrity().await {
+            Err(FileError::ChecksumMismatch {
+                expected,
+                actual,
+                algorithm,
+            }) => {
+                assert_eq!(expected, "deadbeefdeadbeefdeadbeefdeadbeef");
+                assert_ne!(actual, expected);
+                assert_eq!(algorithm, "md5");
+            }
+            other => panic!("expected ChecksumMismatch, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksum_and_content_address() {
+        let data = Bytes::from("hello world");
+        let file = File::from_bytes(data, None).await.unwrap();
+
+        let address = file.content_address().await.unwrap();
+        assert_eq!(address, file.checksum().await.unwrap());
+        assert!(file.verify_checksum(&address).await.unwrap());
+        assert!(file.verify_checksum(&address.to_uppercase()).await.unwrap());
+        assert!(!file.verify_checksum("0000000000000000000000000000000000000000000000000000000000000000").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_save_by_checksum_dedups_identical_content() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-cas-{}-{}",
+            std::process::id(),
+            random_hex_suffix()
+        ));
+        let dir_str = dir.to_str().unwrap();
+
+        let file = File::from_bytes(Bytes::from("same content"), None)
+            .await
+            .unwrap();
+        let address = file.content_address().await.unwrap();
+
+        let (_, saved_first) = file.save_by_checksum(dir_str).await.unwrap();
+        assert!(saved_first.path().unwrap().contains(&address));
+
+        // Saving identical content again must not error and should resolve
+        // to the same path rather than writing a second copy.
+        let (_, saved_second) = file.save_by_checksum(dir_str).await.unwrap();
+        assert_eq!(saved_first.path(), saved_second.path());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_by_checksum_creates_missing_dir() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-cas-mkdir-{}-{}",
+            std::process::id(),
+            random_hex_suffix()
+        ));
+
+        let file = File::from_bytes(Bytes::from("content"), None)
+            .await
+            .unwrap();
+        let (_, saved) = file.save_by_checksum(dir.to_str().unwrap()).await.unwrap();
+        assert_eq!(saved.read_text().await.unwrap(), "content");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_invalid_method() {
+        let file = File::from_bytes(Bytes::from("content"), None)
+            .await
+            .unwrap();
+        let err = file.upload("http://example.invalid/x", "NOT A METHOD").await;
+        assert!(err.is_err());
+    }
+
     #[tokio::test]
     async fn test_set_metadata() {
         let data = Bytes::from("test");
@@ -1002,6 +3433,124 @@ mod tests {
         assert_eq!(file.mime_type(), Some("text/plain"));
     }
 
+    #[tokio::test]
+    async fn test_is_expired_and_set_expire_in() {
+        let mut file = File::from_bytes(Bytes::from("test"), None).await.unwrap();
+        assert!(!file.is_expired());
+
+        file.set_metadata(Metadata {
+            expires_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+            ..Default::default()
+        });
+        assert!(file.is_expired());
+
+        file.clear_expiry();
+        assert!(!file.is_expired());
+        assert!(file.expires_at().is_none());
+
+        file.set_expire_in("1h").unwrap();
+        assert!(!file.is_expired());
+        assert!(file.expires_at().unwrap() > Utc::now());
+
+        assert!(file.set_expire_in("nonsense").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_and_save_reject_expired_file() {
+        let mut file = File::from_bytes(Bytes::from("gone"), None).await.unwrap();
+        file.set_metadata(Metadata {
+            expires_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            file.read().await,
+            Err(FileError::Expired { .. })
+        ));
+        assert!(matches!(
+            file.read_text().await,
+            Err(FileError::Expired { .. })
+        ));
+        assert!(matches!(
+            file.read_stream().await,
+            Err(FileError::Expired { .. })
+        ));
+        assert!(matches!(
+            file.read_range(0, 2).await,
+            Err(FileError::Expired { .. })
+        ));
+        assert!(matches!(
+            file.checksum().await,
+            Err(FileError::Expired { .. })
+        ));
+        assert!(matches!(
+            file.upload("https://example.invalid/upload", "PUT").await,
+            Err(FileError::Expired { .. })
+        ));
+
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-expired-save-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let destination = dir.join("gone.txt");
+        assert!(matches!(
+            file.save(destination.to_str().unwrap()).await,
+            Err(FileError::Expired { .. })
+        ));
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_unique_rejects_expired_file() {
+        let mut file = File::from_bytes(Bytes::from("gone"), None).await.unwrap();
+        file.set_metadata(Metadata {
+            expires_at: Some(Utc::now() - chrono::Duration::seconds(1)),
+            ..Default::default()
+        });
+
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-expired-save-unique-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let destination = dir.join("gone.txt");
+
+        assert!(matches!(
+            file.save_unique(destination.to_str().unwrap()).await,
+            Err(FileError::Expired { .. })
+        ));
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expires_at_round_trips_through_save() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-expiry-roundtrip-{}-{}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let mut file = File::from_bytes(Bytes::from("still here"), None).await.unwrap();
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        file.set_metadata(Metadata {
+            expires_at: Some(expires_at),
+            ..Default::default()
+        });
+
+        let destination = dir.join("still-here.txt");
+        let (_, saved) = file.save(destination.to_str().unwrap()).await.unwrap();
+        assert_eq!(
+            saved.expires_at().unwrap().timestamp_millis(),
+            expires_at.timestamp_millis()
+        );
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_display() {
         let data = Bytes::from("test");
@@ -1015,6 +3564,18 @@ mod tests {
         assert!(display.contains("test.txt"));
     }
 
+    #[tokio::test]
+    async fn test_to_string_pretty_with_tags() {
+        let file = File::from_bytes(Bytes::from("test"), None).await.unwrap();
+        assert!(!file.to_string_pretty().contains("tags"));
+
+        let mut tags = BTreeMap::new();
+        tags.insert("env".to_string(), "prod".to_string());
+        let with_tags = file.to_string_pretty_with_tags(Some(&tags));
+        assert!(with_tags.contains("\"tags\""));
+        assert!(with_tags.contains("\"env\":\"prod\""));
+    }
+
     #[tokio::test]
     async fn test_from_stream() {
         let chunks = vec![Ok(Bytes::from("hello ")), Ok(Bytes::from("world"))];
@@ -1025,4 +3586,454 @@ mod tests {
         assert_eq!(text, "hello world");
         assert_eq!(file.size(), Some(11));
     }
+
+    #[tokio::test]
+    async fn test_read_stream_buffered() {
+        let data = Bytes::from("hello world");
+        let file = File::from_bytes(data.clone(), None).await.unwrap();
+
+        let mut stream = file.read_stream().await.unwrap();
+        let mut collected = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            collected.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(Bytes::from(collected), data);
+    }
+
+    #[tokio::test]
+    async fn test_read_text_sniffs_utf8_bom() {
+        let mut data = vec![0xEF, 0xBB, 0xBF];
+        data.extend_from_slice("hello".as_bytes());
+        let file = File::from_bytes(Bytes::from(data), None).await.unwrap();
+        assert_eq!(file.read_text().await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_read_text_sniffs_utf16le_bom() {
+        let mut data = vec![0xFF, 0xFE];
+        for unit in "hi".encode_utf16() {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        let file = File::from_bytes(Bytes::from(data), None).await.unwrap();
+        assert_eq!(file.read_text().await.unwrap(), "hi");
+    }
+
+    #[tokio::test]
+    async fn test_read_text_honors_mime_charset() {
+        let (encoded, _, _) = encoding_rs::WINDOWS_1252.encode("café");
+        let hint = Metadata {
+            mime_type: Some("text/plain; charset=windows-1252".to_string()),
+            ..Default::default()
+        };
+        let file = File::from_bytes(Bytes::from(encoded.into_owned()), Some(hint))
+            .await
+            .unwrap();
+        assert_eq!(file.read_text().await.unwrap(), "café");
+    }
+
+    #[tokio::test]
+    async fn test_read_text_with_encoding_strict_errors_on_malformed() {
+        let data = Bytes::from(vec![0x80, 0x81, 0x82]);
+        let file = File::from_bytes(data, None).await.unwrap();
+        assert!(file
+            .read_text_with_encoding(Some(encoding_rs::UTF_8), true)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_slice_range() {
+        let data = Bytes::from("hello world");
+        assert_eq!(slice_range(&data, 0, 5), Bytes::from("hello"));
+        assert_eq!(slice_range(&data, 6, 5), Bytes::from("world"));
+        assert_eq!(slice_range(&data, 6, 100), Bytes::from("world"));
+        assert_eq!(slice_range(&data, 100, 5), Bytes::new());
+    }
+
+    #[tokio::test]
+    async fn test_read_range_in_memory_source() {
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+        let range = file.read_range(6, 5).await.unwrap();
+        assert_eq!(range.data, Bytes::from("world"));
+        assert!(range.range_honored);
+    }
+
+    #[tokio::test]
+    async fn test_read_range_file_source() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-range-{}-{}",
+            std::process::id(),
+            random_hex_suffix()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("range.txt");
+        tokio::fs::write(&path, "hello world").await.unwrap();
+
+        let file = File::from_file(&path, None).await.unwrap();
+        let range = file.read_range(6, 5).await.unwrap();
+        assert_eq!(range.data, Bytes::from("world"));
+        assert!(range.range_honored);
+
+        // Requesting past the end returns whatever bytes remain.
+        let tail = file.read_range(6, 100).await.unwrap();
+        assert_eq!(tail.data, Bytes::from("world"));
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_range_bounds_variants() {
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            file.read_range_bounds(6..).await.unwrap().data,
+            Bytes::from("world")
+        );
+        assert_eq!(
+            file.read_range_bounds(..5).await.unwrap().data,
+            Bytes::from("hello")
+        );
+        assert_eq!(
+            file.read_range_bounds(0..=4).await.unwrap().data,
+            Bytes::from("hello")
+        );
+        assert_eq!(
+            file.read_range_bounds(..).await.unwrap().data,
+            Bytes::from("hello world")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_range_bounds_start_past_size_errors() {
+        let file = File::from_bytes(Bytes::from("hi"), None).await.unwrap();
+        assert!(file.read_range_bounds(100..).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_chunks_splits_into_requested_sizes() {
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+
+        let mut stream = file.chunks(4).await.unwrap();
+        let mut collected = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            collected.push(chunk.unwrap());
+        }
+
+        assert_eq!(collected, vec![
+            Bytes::from("hell"),
+            Bytes::from("o wo"),
+            Bytes::from("rld"),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_chunks_rejects_zero_size() {
+        let file = File::from_bytes(Bytes::from("hi"), None).await.unwrap();
+        assert!(file.chunks(0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compress_decompress_zstd_roundtrip() {
+        let hint = Metadata {
+            name: Some("notes.txt".to_string()),
+            ..Default::default()
+        };
+        let file = File::from_bytes(Bytes::from("hello world"), Some(hint))
+            .await
+            .unwrap();
+
+        let compressed = file.compress(CompressionAlgorithm::Zstd).await.unwrap();
+        assert_eq!(compressed.mime_type(), Some("application/zstd"));
+        assert_eq!(compressed.name(), Some("notes.txt.zst"));
+
+        let decompressed = compressed.decompress().await.unwrap();
+        assert_eq!(decompressed.read_text().await.unwrap(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_compress_decompress_gzip_roundtrip() {
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+
+        let compressed = file.compress(CompressionAlgorithm::Gzip).await.unwrap();
+        assert_eq!(compressed.mime_type(), Some("application/gzip"));
+
+        let decompressed = compressed.decompress().await.unwrap();
+        assert_eq!(decompressed.read_text().await.unwrap(), "hello world");
+    }
+
+    #[tokio::test]
+    async fn test_decompress_non_compressed_errors() {
+        let file = File::from_bytes(Bytes::from("hello world"), None)
+            .await
+            .unwrap();
+        assert!(file.decompress().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tar_roundtrip() {
+        let a = File::from_bytes(
+            Bytes::from("file a"),
+            Some(Metadata {
+                name: Some("a.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let b = File::from_bytes(
+            Bytes::from("file b"),
+            Some(Metadata {
+                name: Some("b.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        let archive = File::from_tar(&[a, b], None).await.unwrap();
+        assert_eq!(archive.extension(), Some("tar"));
+
+        let entries = archive.tar_entries().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name(), Some("a.txt"));
+        assert_eq!(entries[0].read_text().await.unwrap(), "file a");
+        assert_eq!(entries[1].name(), Some("b.txt"));
+        assert_eq!(entries[1].read_text().await.unwrap(), "file b");
+    }
+
+    #[tokio::test]
+    async fn test_tar_roundtrip_with_compression() {
+        let a = File::from_bytes(
+            Bytes::from("hello"),
+            Some(Metadata {
+                name: Some("hello.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        let archive = File::from_tar(&[a], Some(CompressionAlgorithm::Zstd))
+            .await
+            .unwrap();
+        assert_eq!(archive.mime_type(), Some("application/zstd"));
+
+        let decompressed = archive.decompress().await.unwrap();
+        let entries = decompressed.tar_entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].read_text().await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_tar_entries_stream_matches_tar_entries() {
+        let a = File::from_bytes(
+            Bytes::from("file a"),
+            Some(Metadata {
+                name: Some("a.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let b = File::from_bytes(
+            Bytes::from("file b"),
+            Some(Metadata {
+                name: Some("b.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        let archive = File::from_tar(&[a, b], None).await.unwrap();
+
+        let streamed: Vec<File> = archive
+            .tar_entries_stream()
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(streamed.len(), 2);
+        assert_eq!(streamed[0].name(), Some("a.txt"));
+        assert_eq!(streamed[0].read_text().await.unwrap(), "file a");
+        assert_eq!(streamed[1].name(), Some("b.txt"));
+        assert_eq!(streamed[1].read_text().await.unwrap(), "file b");
+    }
+
+    #[tokio::test]
+    async fn test_from_tar_requires_name() {
+        let unnamed = File::from_bytes(Bytes::from("data"), None).await.unwrap();
+        assert!(File::from_tar(&[unnamed], None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_and_extract_tar_archive() {
+        let a = File::from_bytes(
+            Bytes::from("file a"),
+            Some(Metadata {
+                name: Some("a.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        let archive = File::from_tar(&[a], None).await.unwrap();
+
+        let entries = archive.list_archive().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "a.txt");
+        assert_eq!(entries[0].size, 6);
+
+        let extracted = archive.extract_entry("a.txt").await.unwrap();
+        assert_eq!(extracted.read_text().await.unwrap(), "file a");
+    }
+
+    #[tokio::test]
+    async fn test_list_and_extract_compressed_tar_archive() {
+        let a = File::from_bytes(
+            Bytes::from("hello"),
+            Some(Metadata {
+                name: Some("hello.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+
+        let archive = File::from_tar(&[a], Some(CompressionAlgorithm::Gzip))
+            .await
+            .unwrap();
+
+        let entries = archive.list_archive().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "hello.txt");
+
+        let extracted = archive.extract_entry("hello.txt").await.unwrap();
+        assert_eq!(extracted.read_text().await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_extract_entry_not_found() {
+        let a = File::from_bytes(
+            Bytes::from("file a"),
+            Some(Metadata {
+                name: Some("a.txt".to_string()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let archive = File::from_tar(&[a], None).await.unwrap();
+
+        assert!(archive.extract_entry("missing.txt").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_archive_rejects_non_archive() {
+        let not_an_archive = File::from_bytes(Bytes::from("just some text"), None)
+            .await
+            .unwrap();
+        assert!(not_an_archive.list_archive().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_and_extract_zip_archive() {
+        use std::io::Write;
+
+        let mut buf = Vec::new();
+        {
+            let mut writer = zip::ZipWriter::new(std::io::Cursor::new(&mut buf));
+            let options = zip::write::FileOptions::default();
+            writer.start_file("a.txt", options).unwrap();
+            writer.write_all(b"file a").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let archive = File::from_bytes(Bytes::from(buf), None).await.unwrap();
+
+        let entries = archive.list_archive().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "a.txt");
+        assert_eq!(entries[0].size, 6);
+
+        let extracted = archive.extract_entry("a.txt").await.unwrap();
+        assert_eq!(extracted.read_text().await.unwrap(), "file a");
+    }
+
+    #[test]
+    fn test_strip_unique_suffix() {
+        assert_eq!(strip_unique_suffix("report-0x0123456789abcdef"), "report");
+        assert_eq!(strip_unique_suffix("report"), "report");
+        assert_eq!(strip_unique_suffix("report-0xtooshort"), "report-0xtooshort");
+    }
+
+    #[test]
+    fn test_unique_candidate_path_does_not_stack_suffixes() {
+        let first = unique_candidate_path("dir/report.txt");
+        assert!(first.starts_with("dir/report-0x"));
+        assert!(first.ends_with(".txt"));
+
+        let second = unique_candidate_path(&first);
+        let stem = Path::new(&second).file_stem().unwrap().to_str().unwrap();
+        assert_eq!(stem.matches("-0x").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_save_unique_avoids_collision() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-{}-{}",
+            std::process::id(),
+            random_hex_suffix()
+        ));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let destination = dir.join("report.txt");
+        let destination = destination.to_str().unwrap();
+
+        let first = File::from_bytes(Bytes::from("first"), None).await.unwrap();
+        let (_, saved_first) = first.save_unique(destination).await.unwrap();
+        assert_eq!(saved_first.path(), Some(destination));
+
+        let second = File::from_bytes(Bytes::from("second"), None).await.unwrap();
+        let (_, saved_second) = second.save_unique(destination).await.unwrap();
+        let second_path = saved_second.path().unwrap();
+        assert_ne!(second_path, destination);
+        assert!(second_path.contains("-0x"));
+
+        assert_eq!(saved_first.read_text().await.unwrap(), "first");
+        assert_eq!(saved_second.read_text().await.unwrap(), "second");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_save_unique_creates_missing_parent_directory() {
+        let dir = std::env::temp_dir().join(format!(
+            "smooai-file-test-mkdir-{}-{}",
+            std::process::id(),
+            random_hex_suffix()
+        ));
+        let destination = dir.join("nested").join("report.txt");
+        let destination = destination.to_str().unwrap();
+
+        let file = File::from_bytes(Bytes::from("content"), None)
+            .await
+            .unwrap();
+        let (_, saved) = file.save_unique(destination).await.unwrap();
+        assert_eq!(saved.read_text().await.unwrap(), "content");
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
 }
@@ -27,11 +27,15 @@
 //! # }
 //! ```
 
+pub mod archive;
 pub mod content_disposition;
+pub mod content_store;
+pub mod data_url;
 pub mod detection;
 pub mod error;
 pub mod file;
 pub mod metadata;
+pub mod object_store;
 pub mod source;
 
 // Re-export primary types at the crate root for convenience.
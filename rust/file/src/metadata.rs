@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::error::{FileError, Result};
+
 /// Represents metadata about a file including its properties and attributes.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metadata {
@@ -41,6 +43,16 @@ pub struct Metadata {
     /// When the file was created.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
+
+    /// An opaque, provider-specific version token (e.g. a GCS object generation number).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// When the file's contents should be considered gone, as checked by
+    /// [`crate::file::File::is_expired`]. Populated from a fetched `Expires`
+    /// header, or set directly via [`crate::file::File::set_metadata`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl Metadata {
@@ -49,6 +61,26 @@ impl Metadata {
         Self::default()
     }
 
+    /// Parse `mime_type` into a [`mime::Mime`], exposing its top-level type
+    /// (`image`), subtype (`svg`), suffix (`xml`), and parameters (e.g.
+    /// `charset`) without re-parsing the string at every call site.
+    ///
+    /// `mime_type` stays a plain `String` so the existing JSON representation
+    /// is unaffected; this is a derived view over it, re-parsed on each call.
+    pub fn mime(&self) -> Option<mime::Mime> {
+        self.mime_type.as_deref()?.parse().ok()
+    }
+
+    /// Whether the parsed MIME type's top-level type is `image`.
+    pub fn is_image(&self) -> bool {
+        self.mime().is_some_and(|m| m.type_() == mime::IMAGE)
+    }
+
+    /// Whether the parsed MIME type's top-level type is `text`.
+    pub fn is_text(&self) -> bool {
+        self.mime().is_some_and(|m| m.type_() == mime::TEXT)
+    }
+
     /// Merges another metadata (hints) into this one. Values from `other`
     /// only fill in fields that are currently `None` in `self`.
     pub fn merge_hints(&mut self, other: &MetadataHint) {
@@ -79,6 +111,12 @@ impl Metadata {
         if self.created_at.is_none() {
             self.created_at = other.created_at;
         }
+        if self.version.is_none() {
+            self.version.clone_from(&other.version);
+        }
+        if self.expires_at.is_none() {
+            self.expires_at = other.expires_at;
+        }
     }
 }
 
@@ -86,6 +124,35 @@ impl Metadata {
 /// All fields are optional and mirror those in [`Metadata`].
 pub type MetadataHint = Metadata;
 
+/// Parse a short human-friendly duration directive (e.g. `"5ms"`, `"30s"`,
+/// `"2h"`, `"30d"`) into a [`chrono::Duration`], for use with
+/// [`crate::file::File::set_expire_in`].
+///
+/// Supported units: `ms` (milliseconds), `s` (seconds), `m` (minutes), `h`
+/// (hours), `d` (days).
+pub fn parse_expire_directive(directive: &str) -> Result<chrono::Duration> {
+    let directive = directive.trim();
+    let split_at = directive.find(|c: char| !c.is_ascii_digit()).ok_or_else(|| {
+        FileError::InvalidSource(format!("Invalid expire directive: '{}'", directive))
+    })?;
+    let (amount, unit) = directive.split_at(split_at);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| FileError::InvalidSource(format!("Invalid expire directive: '{}'", directive)))?;
+
+    match unit {
+        "ms" => Ok(chrono::Duration::milliseconds(amount)),
+        "s" => Ok(chrono::Duration::seconds(amount)),
+        "m" => Ok(chrono::Duration::minutes(amount)),
+        "h" => Ok(chrono::Duration::hours(amount)),
+        "d" => Ok(chrono::Duration::days(amount)),
+        other => Err(FileError::InvalidSource(format!(
+            "Unknown expire directive unit '{}' in '{}'",
+            other, directive
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +169,8 @@ mod tests {
         assert!(m.hash.is_none());
         assert!(m.last_modified.is_none());
         assert!(m.created_at.is_none());
+        assert!(m.version.is_none());
+        assert!(m.expires_at.is_none());
     }
 
     #[test]
@@ -147,6 +216,92 @@ mod tests {
         assert!(!json.contains("\"url\""));
     }
 
+    #[test]
+    fn test_mime_parses_type_and_params() {
+        let m = Metadata {
+            mime_type: Some("text/plain; charset=utf-8".to_string()),
+            ..Default::default()
+        };
+        let mime = m.mime().unwrap();
+        assert_eq!(mime.type_(), mime::TEXT);
+        assert_eq!(mime.subtype(), mime::PLAIN);
+        assert_eq!(mime.get_param("charset").map(|v| v.as_str()), Some("utf-8"));
+    }
+
+    #[test]
+    fn test_mime_none_when_unset_or_unparseable() {
+        assert!(Metadata::new().mime().is_none());
+        let m = Metadata {
+            mime_type: Some("not a mime type".to_string()),
+            ..Default::default()
+        };
+        assert!(m.mime().is_none());
+    }
+
+    #[test]
+    fn test_is_image_and_is_text() {
+        let image = Metadata {
+            mime_type: Some("image/png".to_string()),
+            ..Default::default()
+        };
+        assert!(image.is_image());
+        assert!(!image.is_text());
+
+        let text = Metadata {
+            mime_type: Some("text/plain".to_string()),
+            ..Default::default()
+        };
+        assert!(text.is_text());
+        assert!(!text.is_image());
+
+        assert!(!Metadata::new().is_image());
+        assert!(!Metadata::new().is_text());
+    }
+
+    #[test]
+    fn test_merge_hints_fills_expires_at() {
+        let mut m = Metadata::new();
+        let expires = Utc::now();
+        let hint = Metadata {
+            expires_at: Some(expires),
+            ..Default::default()
+        };
+        m.merge_hints(&hint);
+        assert_eq!(m.expires_at, Some(expires));
+    }
+
+    #[test]
+    fn test_parse_expire_directive_units() {
+        assert_eq!(
+            parse_expire_directive("5ms").unwrap(),
+            chrono::Duration::milliseconds(5)
+        );
+        assert_eq!(
+            parse_expire_directive("30s").unwrap(),
+            chrono::Duration::seconds(30)
+        );
+        assert_eq!(
+            parse_expire_directive("10m").unwrap(),
+            chrono::Duration::minutes(10)
+        );
+        assert_eq!(
+            parse_expire_directive("2h").unwrap(),
+            chrono::Duration::hours(2)
+        );
+        assert_eq!(
+            parse_expire_directive("30d").unwrap(),
+            chrono::Duration::days(30)
+        );
+    }
+
+    #[test]
+    fn test_parse_expire_directive_rejects_invalid_input() {
+        assert!(parse_expire_directive("").is_err());
+        assert!(parse_expire_directive("5").is_err());
+        assert!(parse_expire_directive("ms").is_err());
+        assert!(parse_expire_directive("5weeks").is_err());
+    }
+
     #[test]
     fn test_deserialize() {
         let json = r#"{"name":"test.txt","mime_type":"text/plain","size":100}"#;
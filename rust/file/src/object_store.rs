@@ -0,0 +1,508 @@
+//! Backend-agnostic object storage abstraction.
+//!
+//! [`ObjectStore`] lets [`crate::file::File`] read and write objects across
+//! Amazon S3, Azure Blob Storage, and Google Cloud Storage through a single
+//! interface, selected by URL scheme (`s3://`, `az://`, `gs://`).
+
+use async_trait::async_trait;
+use aws_sdk_s3::Client as S3Client;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+use crate::error::{FileError, Result};
+
+/// The result of fetching an object from an [`ObjectStore`].
+#[derive(Debug, Clone)]
+pub struct ObjectStoreObject {
+    /// The raw object bytes.
+    pub data: Bytes,
+    /// The provider-reported content type, if any.
+    pub content_type: Option<String>,
+    /// The provider-reported content length, if any.
+    pub content_length: Option<u64>,
+    /// The provider-reported ETag, if any.
+    pub etag: Option<String>,
+    /// The provider-reported last-modified timestamp, if any.
+    pub last_modified: Option<DateTime<Utc>>,
+    /// The provider-reported Content-Disposition header, if any.
+    pub content_disposition: Option<String>,
+    /// An opaque, provider-specific version token (e.g. a GCS generation number).
+    pub version: Option<String>,
+}
+
+/// A backend-agnostic object storage client.
+///
+/// Implementations map provider-specific response fields into the shared
+/// [`ObjectStoreObject`] shape so that callers (e.g. [`crate::file::File`])
+/// don't need to special-case any one cloud provider.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    /// Fetch an object's bytes and metadata.
+    async fn get(&self, bucket: &str, key: &str) -> Result<ObjectStoreObject>;
+
+    /// Upload an object, optionally setting its content type.
+    async fn put(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<()>;
+
+    /// List object keys under a prefix.
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<String>>;
+}
+
+/// An [`ObjectStore`] backed by Amazon S3.
+pub struct S3Store {
+    client: S3Client,
+}
+
+impl S3Store {
+    /// Create a new `S3Store` from an existing S3 client.
+    pub fn new(client: S3Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3Store {
+    async fn get(&self, bucket: &str, key: &str) -> Result<ObjectStoreObject> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        let content_type = resp.content_type().map(|s| s.to_string());
+        let content_length = resp.content_length().and_then(|cl| {
+            if cl > 0 {
+                Some(cl as u64)
+            } else {
+                None
+            }
+        });
+        let etag = resp
+            .e_tag()
+            .map(|e| e.trim_matches('"').to_string());
+        let content_disposition = resp.content_disposition().map(|s| s.to_string());
+        let last_modified = resp.last_modified().and_then(|lm| {
+            DateTime::from_timestamp(lm.secs(), lm.subsec_nanos())
+        });
+
+        let body_bytes = resp
+            .body
+            .collect()
+            .await
+            .map_err(|e| FileError::S3(format!("Failed to read S3 body: {}", e)))?
+            .into_bytes();
+
+        Ok(ObjectStoreObject {
+            data: Bytes::from(body_bytes.to_vec()),
+            content_type,
+            content_length,
+            etag,
+            last_modified,
+            content_disposition,
+            version: None,
+        })
+    }
+
+    async fn put(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<()> {
+        let mut req = self
+            .client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(data.into());
+
+        if let Some(ct) = content_type {
+            req = req.content_type(ct);
+        }
+
+        req.send().await.map_err(|e| FileError::S3(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<String>> {
+        let resp = self
+            .client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .send()
+            .await
+            .map_err(|e| FileError::S3(e.to_string()))?;
+
+        Ok(resp
+            .contents()
+            .iter()
+            .filter_map(|obj| obj.key().map(|k| k.to_string()))
+            .collect())
+    }
+}
+
+/// An [`ObjectStore`] backed by Azure Blob Storage, addressed by storage
+/// account name and authenticated with a SAS token. Shared-key (account
+/// key) request signing is not performed.
+pub struct AzureBlobStore {
+    account: String,
+    credential: String,
+    http: reqwest::Client,
+}
+
+impl AzureBlobStore {
+    /// Create a new `AzureBlobStore` for the given storage account. `credential`
+    /// is a SAS token, appended verbatim as each request's query string.
+    pub fn new(account: impl Into<String>, credential: impl Into<String>) -> Self {
+        Self {
+            account: account.into(),
+            credential: credential.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    fn blob_url(&self, bucket: &str, key: &str) -> String {
+        format!(
+            "https://{}.blob.core.windows.net/{}/{}?{}",
+            self.account,
+            urlencoding_encode(bucket),
+            encode_blob_key(key),
+            self.credential
+        )
+    }
+}
+
+#[async_trait]
+impl ObjectStore for AzureBlobStore {
+    async fn get(&self, bucket: &str, key: &str) -> Result<ObjectStoreObject> {
+        let resp = self
+            .http
+            .get(self.blob_url(bucket, key))
+            .send()
+            .await?
+            .error_for_status()?;
+        let headers = resp.headers().clone();
+        let data = resp.bytes().await?;
+
+        Ok(ObjectStoreObject {
+            content_length: Some(data.len() as u64),
+            data,
+            content_type: headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            etag: headers
+                .get("etag")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.trim_matches('"').to_string()),
+            last_modified: headers
+                .get("last-modified")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| DateTime::parse_from_rfc2822(s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            content_disposition: headers
+                .get("content-disposition")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            version: None,
+        })
+    }
+
+    async fn put(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<()> {
+        let mut req = self
+            .http
+            .put(self.blob_url(bucket, key))
+            .header("x-ms-blob-type", "BlockBlob")
+            .body(data);
+
+        if let Some(ct) = content_type {
+            req = req.header("Content-Type", ct);
+        }
+
+        req.send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<String>> {
+        let url = format!(
+            "https://{}.blob.core.windows.net/{}?restype=container&comp=list&prefix={}&{}",
+            self.account,
+            urlencoding_encode(bucket),
+            urlencoding_encode(prefix),
+            self.credential
+        );
+        let body = self
+            .http
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        Ok(parse_blob_names(&body))
+    }
+}
+
+/// An [`ObjectStore`] backed by Google Cloud Storage, authenticated with a
+/// bearer OAuth2 access token.
+pub struct GcsStore {
+    access_token: String,
+    http: reqwest::Client,
+}
+
+impl GcsStore {
+    /// Create a new `GcsStore` using a bearer OAuth2 access token.
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for GcsStore {
+    async fn get(&self, bucket: &str, key: &str) -> Result<ObjectStoreObject> {
+        let url = format!(
+            "https://storage.googleapis.com/storage/v1/b/{}/o/{}?alt=media",
+            bucket,
+            urlencoding_encode(key)
+        );
+        let resp = self
+            .http
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .error_for_status()?;
+        let headers = resp.headers().clone();
+        let data = resp.bytes().await?;
+
+        Ok(ObjectStoreObject {
+            content_length: Some(data.len() as u64),
+            data,
+            content_type: headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            etag: headers
+                .get("etag")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.trim_matches('"').to_string()),
+            last_modified: headers
+                .get("last-modified")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| DateTime::parse_from_rfc2822(s).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+            content_disposition: headers
+                .get("content-disposition")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+            // GCS exposes the object generation number via `x-goog-generation`,
+            // surfaced here as an opaque version token.
+            version: headers
+                .get("x-goog-generation")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string()),
+        })
+    }
+
+    async fn put(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<()> {
+        let url = format!(
+            "https://storage.googleapis.com/upload/storage/v1/b/{}/o?uploadType=media&name={}",
+            bucket,
+            urlencoding_encode(key)
+        );
+        let mut req = self.http.post(&url).bearer_auth(&self.access_token).body(data);
+        if let Some(ct) = content_type {
+            req = req.header("Content-Type", ct);
+        }
+        req.send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    async fn list(&self, bucket: &str, prefix: &str) -> Result<Vec<String>> {
+        #[derive(serde::Deserialize)]
+        struct Item {
+            name: String,
+        }
+        #[derive(serde::Deserialize)]
+        struct ListResponse {
+            #[serde(default)]
+            items: Vec<Item>,
+        }
+
+        let url = format!(
+            "https://storage.googleapis.com/storage/v1/b/{}/o?prefix={}",
+            bucket,
+            urlencoding_encode(prefix)
+        );
+        let resp: ListResponse = self
+            .http
+            .get(&url)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        Ok(resp.items.into_iter().map(|i| i.name).collect())
+    }
+}
+
+/// Extract each blob name from an Azure `List Blobs` XML response body.
+///
+/// The response shape is `<Blobs><Blob><Name>...</Name>...</Blob>...</Blobs>`;
+/// this scans for that one shape rather than pulling in a full XML parser,
+/// since this crate has no XML parsing dependency. Does not follow
+/// `NextMarker`, so only the first page of results is returned (matching
+/// [`GcsStore::list`], which is likewise unpaginated).
+fn parse_blob_names(xml: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = xml;
+
+    while let Some(blob_start) = rest.find("<Blob>") {
+        let after_blob = &rest[blob_start + "<Blob>".len()..];
+        let Some(blob_end) = after_blob.find("</Blob>") else {
+            break;
+        };
+        let blob_body = &after_blob[..blob_end];
+
+        if let Some(name_start) = blob_body.find("<Name>") {
+            let after_name = &blob_body[name_start + "<Name>".len()..];
+            if let Some(name_end) = after_name.find("</Name>") {
+                names.push(decode_xml_entities(&after_name[..name_end]));
+            }
+        }
+
+        rest = &after_blob[blob_end + "</Blob>".len()..];
+    }
+
+    names
+}
+
+/// Decode the handful of entities XML escapes blob names with.
+fn decode_xml_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Minimal percent-encoding for path segments used in GCS REST URLs.
+fn urlencoding_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+/// Percent-encode an Azure blob name for use in a URL path, preserving `/` as
+/// a path separator (blob names routinely use `/` to form virtual
+/// directories, e.g. `"path/to/file.txt"`) while still encoding special
+/// characters within each segment the same way `urlencoding_encode` does.
+fn encode_blob_key(key: &str) -> String {
+    key.split('/').map(urlencoding_encode).collect::<Vec<_>>().join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blob_names_extracts_names_in_order() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://myaccount.blob.core.windows.net/" ContainerName="mycontainer">
+  <Blobs>
+    <Blob>
+      <Name>path/to/file.txt</Name>
+      <Properties><Content-Length>42</Content-Length></Properties>
+    </Blob>
+    <Blob>
+      <Name>a &amp; b.txt</Name>
+    </Blob>
+  </Blobs>
+  <NextMarker/>
+</EnumerationResults>"#;
+        assert_eq!(
+            parse_blob_names(xml),
+            vec!["path/to/file.txt".to_string(), "a & b.txt".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_blob_names_empty_when_no_blobs() {
+        let xml = r#"<EnumerationResults><Blobs /></EnumerationResults>"#;
+        assert!(parse_blob_names(xml).is_empty());
+    }
+
+    #[test]
+    fn test_urlencoding_encode_plain() {
+        assert_eq!(urlencoding_encode("hello"), "hello");
+    }
+
+    #[test]
+    fn test_urlencoding_encode_special_chars() {
+        assert_eq!(urlencoding_encode("a/b c"), "a%2Fb%20c");
+    }
+
+    #[test]
+    fn test_azure_blob_url_includes_credential() {
+        let store = AzureBlobStore::new("myaccount", "sv=2020&sig=abc");
+        let url = store.blob_url("mycontainer", "path/to/file.txt");
+        assert_eq!(
+            url,
+            "https://myaccount.blob.core.windows.net/mycontainer/path/to/file.txt?sv=2020&sig=abc"
+        );
+    }
+
+    #[test]
+    fn test_azure_blob_url_percent_encodes_special_chars() {
+        let store = AzureBlobStore::new("myaccount", "sv=2020&sig=abc");
+        let url = store.blob_url("my container", "weird?name#here&now.txt");
+        assert_eq!(
+            url,
+            "https://myaccount.blob.core.windows.net/my%20container/weird%3Fname%23here%26now.txt?sv=2020&sig=abc"
+        );
+    }
+
+    #[test]
+    fn test_azure_blob_url_preserves_slashes_but_encodes_segment_chars() {
+        let store = AzureBlobStore::new("myaccount", "sv=2020&sig=abc");
+        let url = store.blob_url("mycontainer", "a dir/weird?name.txt");
+        assert_eq!(
+            url,
+            "https://myaccount.blob.core.windows.net/mycontainer/a%20dir/weird%3Fname.txt?sv=2020&sig=abc"
+        );
+    }
+}
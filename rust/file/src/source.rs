@@ -16,6 +16,11 @@ pub enum FileSource {
     Stream,
     /// File loaded from Amazon S3.
     S3,
+    /// File loaded via a backend-agnostic [`crate::object_store::ObjectStore`]
+    /// (e.g. Azure Blob Storage or Google Cloud Storage).
+    ObjectStore,
+    /// File decoded from a `data:` URL (RFC 2397).
+    DataUrl,
 }
 
 impl fmt::Display for FileSource {
@@ -26,6 +31,8 @@ impl fmt::Display for FileSource {
             FileSource::File => write!(f, "File"),
             FileSource::Stream => write!(f, "Stream"),
             FileSource::S3 => write!(f, "S3"),
+            FileSource::ObjectStore => write!(f, "ObjectStore"),
+            FileSource::DataUrl => write!(f, "DataUrl"),
         }
     }
 }
@@ -41,6 +48,8 @@ mod tests {
         assert_eq!(FileSource::File.to_string(), "File");
         assert_eq!(FileSource::Stream.to_string(), "Stream");
         assert_eq!(FileSource::S3.to_string(), "S3");
+        assert_eq!(FileSource::ObjectStore.to_string(), "ObjectStore");
+        assert_eq!(FileSource::DataUrl.to_string(), "DataUrl");
     }
 
     #[test]
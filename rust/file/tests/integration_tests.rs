@@ -313,3 +313,387 @@ async fn test_url_full_metadata() {
     assert_eq!(file.hash(), Some("hash123"));
     assert!(file.last_modified().is_some());
 }
+
+/// URL fetch transparently decompresses a gzip `Content-Encoding` body.
+#[tokio::test]
+async fn test_url_content_encoding_gzip_is_decoded() {
+    use std::io::Write;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let body = "plain text served gzip-encoded over the wire";
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body.as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_bytes(compressed.clone())
+                .insert_header("content-type", "text/plain")
+                .insert_header("content-encoding", "gzip")
+                .insert_header("content-length", compressed.len().to_string().as_str()),
+        )
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/compressed.txt", server.uri());
+
+    let file = File::from_url(&url, None).await.unwrap();
+    assert_eq!(file.read_text().await.unwrap(), body);
+    assert_eq!(file.size(), Some(body.len() as u64));
+
+    let raw = File::from_url_with_options(&url, None, false)
+        .await
+        .unwrap();
+    assert_eq!(raw.read().await.unwrap().as_ref(), compressed.as_slice());
+}
+
+/// `read_range` issues a `Range` request and honors a `206` response.
+#[tokio::test]
+async fn test_url_read_range_honored() {
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = "0123456789";
+
+    Mock::given(method("GET"))
+        .and(header("range", "bytes=2-5"))
+        .respond_with(
+            ResponseTemplate::new(206)
+                .set_body_string("2345")
+                .insert_header("content-range", "bytes 2-5/10"),
+        )
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/range.txt", server.uri());
+    let file = File::from_url(&url, None).await.unwrap();
+
+    // Fetching the full body first shouldn't affect a subsequent ranged fetch,
+    // since read_range always issues a fresh request.
+    let _ = body;
+
+    let range = file.read_range(2, 4).await.unwrap();
+    assert_eq!(range.data.as_ref(), b"2345");
+    assert!(range.range_honored);
+}
+
+/// `read_range` surfaces when a server ignores `Range` and returns `200`.
+#[tokio::test]
+async fn test_url_read_range_not_honored() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let body = "0123456789";
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(body))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/whole.txt", server.uri());
+    let file = File::from_url(&url, None).await.unwrap();
+
+    let range = file.read_range(2, 4).await.unwrap();
+    assert!(!range.range_honored);
+    assert_eq!(range.data.as_ref(), body.as_bytes());
+}
+
+/// Pipeline: encode a `File` as a `data:` URL, then decode it back.
+#[tokio::test]
+async fn test_data_url_roundtrip_pipeline() {
+    let content = "hello from a data url";
+    let hint = Metadata {
+        mime_type: Some("text/plain".to_string()),
+        ..Default::default()
+    };
+    let file = File::from_bytes(Bytes::from(content), Some(hint))
+        .await
+        .unwrap();
+
+    let uri = file.to_data_url().await.unwrap();
+    assert!(uri.starts_with("data:text/plain"));
+
+    let decoded = File::from_data_url(&uri, None).await.unwrap();
+    assert_eq!(decoded.source(), FileSource::DataUrl);
+    assert_eq!(decoded.read_text().await.unwrap(), content);
+    assert_eq!(
+        file.checksum().await.unwrap(),
+        decoded.checksum().await.unwrap()
+    );
+}
+
+/// `upload` streams the body to a remote endpoint with the expected headers.
+#[tokio::test]
+async fn test_upload_streams_body_with_headers() {
+    use wiremock::matchers::{body_bytes, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let hint = Metadata {
+        mime_type: Some("text/plain".to_string()),
+        name: Some("notes.txt".to_string()),
+        ..Default::default()
+    };
+    let file = File::from_bytes(Bytes::from("upload me"), Some(hint))
+        .await
+        .unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/upload"))
+        .and(header("content-type", "text/plain"))
+        .and(body_bytes("upload me"))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/upload", server.uri());
+    let response = file.upload(&url, "PUT").await.unwrap();
+    assert_eq!(response.status, 201);
+}
+
+/// `from_url_verified` accepts a download whose ETag matches its contents,
+/// and rejects one whose contents were corrupted in transit.
+#[tokio::test]
+async fn test_from_url_verified_checks_etag() {
+    use digest::Digest;
+    use md5::Md5;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let body = "verified download";
+    let md5_hex = format!("{:x}", Md5::digest(body.as_bytes()));
+
+    let good_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(body)
+                .insert_header("etag", md5_hex.as_str()),
+        )
+        .mount(&good_server)
+        .await;
+    let good_url = format!("{}/ok.txt", good_server.uri());
+    let file = File::from_url_verified(&good_url, None).await.unwrap();
+    assert_eq!(file.read_text().await.unwrap(), body);
+
+    let bad_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("tampered in transit")
+                .insert_header("etag", md5_hex.as_str()),
+        )
+        .mount(&bad_server)
+        .await;
+    let bad_url = format!("{}/bad.txt", bad_server.uri());
+    let err = File::from_url_verified(&bad_url, None).await.unwrap_err();
+    assert!(matches!(err, smooai_file::FileError::ChecksumMismatch { .. }));
+}
+
+/// A URL response's `Expires` header populates `expires_at`, and an
+/// already-passed deadline makes the file unreadable.
+#[tokio::test]
+async fn test_url_expires_header_marks_file_expired() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("stale")
+                .insert_header("expires", "Fri, 01 Jan 2010 00:00:00 GMT"),
+        )
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/stale.txt", server.uri());
+    let file = File::from_url(&url, None).await.unwrap();
+
+    assert!(file.is_expired());
+    assert!(matches!(
+        file.read().await,
+        Err(smooai_file::FileError::Expired { .. })
+    ));
+}
+
+/// Build an S3 client with static test credentials pointed at `endpoint`,
+/// for exercising S3-networked code paths against a `wiremock` server
+/// instead of real AWS.
+fn test_s3_client(endpoint: &str) -> aws_sdk_s3::Client {
+    let credentials =
+        aws_sdk_s3::config::Credentials::new("test-access-key", "test-secret-key", None, None, "test");
+    let config = aws_sdk_s3::config::Builder::new()
+        .behavior_version(aws_config::BehaviorVersion::latest())
+        .region(aws_sdk_s3::config::Region::new("us-east-1"))
+        .endpoint_url(endpoint)
+        .credentials_provider(credentials)
+        .force_path_style(true)
+        .build();
+    aws_sdk_s3::Client::from_conf(config)
+}
+
+/// `upload_to_s3_multipart` drives CreateMultipartUpload -> UploadPart ->
+/// CompleteMultipartUpload against a mock S3-compatible endpoint.
+#[tokio::test]
+async fn test_upload_to_s3_multipart_against_mock_endpoint() {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let bucket = "my-bucket";
+    let key = "uploads/multipart.txt";
+    let upload_id = "test-upload-id";
+    let object_path = format!("/{}/{}", bucket, key);
+    let body = "hello multipart world";
+
+    Mock::given(method("POST"))
+        .and(path(&object_path))
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "<InitiateMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key><UploadId>{}</UploadId></InitiateMultipartUploadResult>",
+            bucket, key, upload_id
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(&object_path))
+        .and(query_param("uploadId", upload_id))
+        .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"part-etag\""))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path(&object_path))
+        .and(query_param("uploadId", upload_id))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "<CompleteMultipartUploadResult><Location>https://example.com</Location><Bucket>{}</Bucket><Key>{}</Key><ETag>\"final-etag\"</ETag></CompleteMultipartUploadResult>",
+            bucket, key
+        )))
+        .mount(&server)
+        .await;
+
+    let client = test_s3_client(&server.uri());
+    let file = File::from_bytes(Bytes::from(body), None).await.unwrap();
+
+    let metadata = file
+        .upload_to_s3_multipart(&client, bucket, key, None)
+        .await
+        .unwrap();
+
+    assert_eq!(metadata.size, Some(body.len() as u64));
+    assert_eq!(metadata.hash.as_deref(), Some("final-etag"));
+    assert_eq!(metadata.url.as_deref(), Some("s3://my-bucket/uploads/multipart.txt"));
+}
+
+/// A failed part upload aborts the multipart upload instead of leaving it
+/// dangling (and orphaning storage charges).
+#[tokio::test]
+async fn test_upload_to_s3_multipart_aborts_on_part_failure() {
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    let bucket = "my-bucket";
+    let key = "uploads/multipart.txt";
+    let upload_id = "test-upload-id";
+    let object_path = format!("/{}/{}", bucket, key);
+
+    Mock::given(method("POST"))
+        .and(path(&object_path))
+        .and(query_param("uploads", ""))
+        .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+            "<InitiateMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key><UploadId>{}</UploadId></InitiateMultipartUploadResult>",
+            bucket, key, upload_id
+        )))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path(&object_path))
+        .and(query_param("uploadId", upload_id))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let abort_mock = Mock::given(method("DELETE"))
+        .and(path(&object_path))
+        .and(query_param("uploadId", upload_id))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1);
+    abort_mock.mount(&server).await;
+
+    let client = test_s3_client(&server.uri());
+    let file = File::from_bytes(Bytes::from("won't make it"), None)
+        .await
+        .unwrap();
+
+    let result = file.upload_to_s3_multipart(&client, bucket, key, None).await;
+    assert!(matches!(result, Err(smooai_file::FileError::S3(_))));
+}
+
+/// `presigned_post` signs a browser-form upload policy locally, without
+/// making any network request.
+#[tokio::test]
+async fn test_presigned_post_signs_policy_locally() {
+    let client = test_s3_client("https://s3.us-east-1.amazonaws.com");
+    let conditions = vec![serde_json::json!(["content-length-range", 0, 1024])];
+
+    let presigned = File::presigned_post(
+        &client,
+        "my-bucket",
+        "uploads/avatar.png",
+        conditions,
+        std::time::Duration::from_secs(600),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(presigned.url, "https://my-bucket.s3.us-east-1.amazonaws.com");
+    assert_eq!(
+        presigned.fields.get("key").map(String::as_str),
+        Some("uploads/avatar.png")
+    );
+    assert_eq!(
+        presigned.fields.get("x-amz-algorithm").map(String::as_str),
+        Some("AWS4-HMAC-SHA256")
+    );
+    assert!(presigned.fields.contains_key("policy"));
+    assert!(presigned.fields.contains_key("x-amz-signature"));
+}
+
+/// `get_signed_url_with_client` produces a SigV4-signed `GetObject` URL for
+/// an S3-sourced file, without making a network request of its own (signing
+/// is purely local; only constructing the S3-sourced `File` via
+/// `from_s3_with_client` touches the mock endpoint).
+#[tokio::test]
+async fn test_get_signed_url_with_client_produces_presigned_url() {
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("report contents"))
+        .mount(&server)
+        .await;
+
+    let client = test_s3_client(&server.uri());
+    let file = File::from_s3_with_client(&client, "my-bucket", "reports/q1.pdf", None)
+        .await
+        .unwrap();
+
+    let url = file
+        .get_signed_url_with_client(&client, "my-bucket", "reports/q1.pdf", 3600)
+        .await
+        .unwrap();
+
+    assert!(url.contains("X-Amz-Signature="));
+    assert!(url.contains("my-bucket"));
+    assert!(url.contains("reports/q1.pdf"));
+}